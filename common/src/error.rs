@@ -0,0 +1,246 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+use crate::Part;
+
+/// An error in the puzzle input that points at the offending token.
+///
+/// Errors are created relative to the fragment of text that is being parsed, usually a single line.
+/// The caller that splits the input into lines then attaches the line with [ParseError::in_line], so the
+/// error can be rendered as a diagnostic with a caret under the token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    message: String,
+    /// Byte span of the offending token within the line.
+    span: Range<usize>,
+    line: Option<Line>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Line {
+    /// 1-based line number
+    number: usize,
+    text: String,
+}
+
+/// Byte offset of `part` inside of `whole`. `part` has to be a subslice of `whole`.
+fn offset_in(whole: &str, part: &str) -> usize {
+    let offset = (part.as_ptr() as usize).wrapping_sub(whole.as_ptr() as usize);
+    assert!(offset + part.len() <= whole.len(), "Token is not part of the parsed text");
+    offset
+}
+
+/// The largest character boundary of `s` at or before `idx`.
+fn floor_char_boundary(s: &str, idx: usize) -> usize {
+    (0..=idx.min(s.len())).rev().find(|&i| s.is_char_boundary(i)).unwrap_or(0)
+}
+
+/// The smallest character boundary of `s` at or after `idx`, or the end of `s`.
+fn ceil_char_boundary(s: &str, idx: usize) -> usize {
+    (idx.min(s.len())..=s.len()).find(|&i| s.is_char_boundary(i)).unwrap_or(s.len())
+}
+
+impl ParseError {
+
+    pub fn new(message: impl Into<String>, span: Range<usize>) -> Self {
+        ParseError {
+            message: message.into(),
+            span,
+            line: None,
+        }
+    }
+
+    /// Create an error pointing at `token`, which must be a subslice of `source`.
+    pub fn at(source: &str, token: &str, message: impl Into<String>) -> Self {
+        let start = offset_in(source, token);
+        Self::new(message, start..start + token.len())
+    }
+
+    /// Create an error pointing just past the end of `source`, for things that are missing.
+    pub fn at_end(source: &str, message: impl Into<String>) -> Self {
+        Self::new(message, source.len()..source.len())
+    }
+
+    /// Attach the line the error occurred in.
+    ///
+    /// `fragment` is the part of the line that was parsed, e.g. the trimmed line, and has to be a
+    /// subslice of `line`. The span is moved from being relative to the fragment to being relative to
+    /// the line, and clamped to the line and its character boundaries so that it can always be rendered.
+    pub fn in_line(mut self, number: usize, line: &str, fragment: &str) -> Self {
+        let offset = offset_in(line, fragment);
+        let start = floor_char_boundary(line, self.span.start + offset);
+        let end = ceil_char_boundary(line, (self.span.end + offset).max(start));
+        self.span = start..end;
+        self.line = Some(Line {
+            number,
+            text: line.to_owned(),
+        });
+        self
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Byte span of the offending token within its line.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// 1-based line number, if known.
+    pub fn line(&self) -> Option<usize> {
+        self.line.as_ref().map(|l| l.number)
+    }
+
+    /// 1-based column of the start of the offending token in characters, if the line is known.
+    pub fn column(&self) -> Option<usize> {
+        self.line.as_ref().map(|l| l.text[..self.span.start].chars().count() + 1)
+    }
+
+    /// Render the error as a multi-line diagnostic with a caret under the offending token.
+    pub fn render(&self) -> String {
+        let Some(line) = &self.line else {
+            return format!("error: {}", self.message);
+        };
+        let gutter = line.number.to_string().len();
+        let column = self.column().unwrap_or(1);
+        let width = line.text[self.span.clone()].chars().count().max(1);
+        format!(
+            "error: {message}\n{:gutter$}--> line {number}, column {column}\n{:gutter$} |\n{number} | {text}\n{:gutter$} | {:pad$}{}",
+            "", "", "", "", "^".repeat(width),
+            message = self.message,
+            number = line.number,
+            text = line.text,
+            pad = column - 1,
+        )
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (self.line(), self.column()) {
+            (Some(line), Some(column)) => write!(f, "line {}, column {}: {}", line, column, self.message),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Everything that can go wrong while solving a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolutionError {
+    /// The input could not be parsed.
+    Parse(ParseError),
+    /// The input was parsed, but it has no answer, e.g. because a number overflowed.
+    NoAnswer(String),
+    /// The part has not been solved yet.
+    Unsolved { day: u8, part: Part },
+    /// The input could not be read while streaming it.
+    Read(String),
+}
+
+impl Display for SolutionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolutionError::Parse(_) => write!(f, "Invalid input"),
+            SolutionError::NoAnswer(reason) => write!(f, "No answer: {}", reason),
+            SolutionError::Unsolved { day, part } => write!(f, "Part {} of day {} is not solved.", part, day),
+            SolutionError::Read(reason) => write!(f, "Could not read the input: {}", reason),
+        }
+    }
+}
+
+impl Error for SolutionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SolutionError::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for SolutionError {
+    fn from(e: ParseError) -> Self {
+        SolutionError::Parse(e)
+    }
+}
+
+/// Integers that can be summed up with [checked_sum].
+pub trait CheckedAdd: Sized {
+    fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! checked_add {
+    ($($t:ty),+) => {
+        $(impl CheckedAdd for $t {
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+        })+
+    };
+}
+
+checked_add!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Add a value to a running sum, failing with [SolutionError::NoAnswer] instead of overflowing.
+pub fn checked_sum<T: CheckedAdd>(sum: T, value: T) -> Result<T, SolutionError> {
+    sum.checked_add(value)
+        .ok_or_else(|| SolutionError::NoAnswer("The sum overflows".to_owned()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn span_of_token() {
+        let line = "  Game 3: 8 purple";
+        let fragment = line.trim();
+        let token = &fragment[12..];
+        let err = ParseError::at(fragment, token, "Unknown color").in_line(3, line, fragment);
+        assert_eq!(err.span(), 14..18);
+        assert_eq!(err.line(), Some(3));
+        assert_eq!(err.column(), Some(15));
+        assert_eq!(err.to_string(), "line 3, column 15: Unknown color");
+        assert_eq!(err.render(), "\
+error: Unknown color
+ --> line 3, column 15
+  |
+3 |   Game 3: 8 purple
+  |               ^^^^");
+    }
+
+    #[test]
+    fn solution_error_source() {
+        let err = SolutionError::from(ParseError::new("Unknown color", 3..5));
+        assert_eq!(err.to_string(), "Invalid input");
+        assert_eq!(err.source().unwrap().to_string(), "Unknown color");
+    }
+
+    #[test]
+    fn span_is_clamped_to_line() {
+        let err = ParseError::new("Too long", 0..50).in_line(1, "abc", "abc");
+        assert_eq!(err.span(), 0..3);
+        assert!(err.render().ends_with("1 | abc\n  | ^^^"));
+        let err = ParseError::new("Inside a char", 2..3).in_line(1, "a€b", "a€b");
+        assert_eq!((err.span(), err.column()), (1..4, Some(2)));
+        let err = ParseError::new("Past the end", 7..9).in_line(2, "ab", "ab");
+        assert_eq!((err.span(), err.column()), (2..2, Some(3)));
+        assert!(err.render().ends_with("2 | ab\n  |   ^"));
+    }
+
+    #[test]
+    fn sum_overflow() {
+        assert_eq!(checked_sum(250u8, 5), Ok(255));
+        assert!(matches!(checked_sum(250u8, 6), Err(SolutionError::NoAnswer(_))));
+        assert!(matches!(checked_sum(i64::MIN, -1), Err(SolutionError::NoAnswer(_))));
+    }
+
+    #[test]
+    fn missing_token() {
+        let err = ParseError::at_end("Game 3", "Missing ':'").in_line(12, "Game 3", "Game 3");
+        assert_eq!(err.column(), Some(7));
+        assert!(err.render().ends_with("12 | Game 3\n   |       ^"));
+    }
+}
@@ -0,0 +1,154 @@
+use crate::ParseError;
+use super::{area_points, in_bounds, inconsistent_lengths, wrap_into, Grid, Neighbourhood, Point};
+
+/// A grid of ASCII cells that borrows the puzzle input instead of copying it.
+///
+/// The input is validated once when the grid is created, which rejects bytes that are not ASCII. After
+/// that every access is a single index computation. Lines end with either `\n` or `\r\n`, the same for all lines, and the last line may or may
+/// not end with one. Unlike parsing a [Grid], lines are not trimmed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteGrid<'a> {
+    data: &'a [u8],
+    width: usize,
+    height: usize,
+    /// The distance between the starts of two rows, including the line ending.
+    stride: usize,
+}
+
+impl<'a> ByteGrid<'a> {
+
+    pub fn new(input: &'a [u8]) -> Result<Self, ParseError> {
+        let mut lines: Vec<&[u8]> = input.split(|b| *b == b'\n').collect();
+        if lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+        let crlf = lines.first().is_some_and(|line| line.ends_with(b"\r"));
+        let width = lines.first().map_or(0, |line| line.len() - usize::from(crlf));
+
+        for (idx, line) in lines.iter().enumerate() {
+            let text = String::from_utf8_lossy(line);
+            let error = |message: String| {
+                let text = text.trim_end_matches('\r');
+                ParseError::at(text, text, message).in_line(idx + 1, text, text)
+            };
+            // The last line does not need to be terminated
+            if crlf != line.ends_with(b"\r") && (idx + 1 < lines.len() || !crlf) {
+                return Err(error("Inconsistent line endings.".to_string()));
+            }
+            let cells = line.strip_suffix(b"\r").unwrap_or(line);
+            if let Some(offset) = cells.iter().position(|b| !b.is_ascii()) {
+                // Everything before the offset is ASCII, so it is at the same offset in the lossy text
+                let c = text[offset..].chars().next().unwrap_or(char::REPLACEMENT_CHARACTER);
+                let token = &text[offset..offset + c.len_utf8()];
+                return Err(ParseError::at(&text, token, format!("Invalid cell '{}', expected ASCII", c))
+                    .in_line(idx + 1, &text, &text));
+            }
+            if cells.len() != width {
+                return Err(error(inconsistent_lengths(width, cells.len())));
+            }
+        }
+
+        Ok(ByteGrid {
+            data: input,
+            width,
+            height: lines.len(),
+            stride: width + if crlf { 2 } else { 1 },
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        in_bounds(self.width, self.height, p)
+    }
+
+    pub fn get(&self, p: Point) -> Option<u8> {
+        self.contains(p).then(|| self.data[p.y as usize * self.stride + p.x as usize])
+    }
+
+    /// The cells of one row, without the line ending.
+    pub fn row(&self, y: usize) -> Option<&'a [u8]> {
+        (y < self.height).then(|| &self.data[y * self.stride..y * self.stride + self.width])
+    }
+
+    pub fn rows(&self) -> impl ExactSizeIterator<Item=&'a [u8]> + '_ {
+        (0..self.height).map(|y| &self.data[y * self.stride..y * self.stride + self.width])
+    }
+
+    /// All positions of the grid in reading order.
+    pub fn points(&self) -> impl Iterator<Item=Point> {
+        area_points(self.width, self.height)
+    }
+
+    /// All positions of the grid with their values, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item=(Point, u8)> + '_ {
+        self.points().map(|p| (p, self.data[p.y as usize * self.stride + p.x as usize]))
+    }
+
+    /// The neighbours of a position with their values, like [Grid::neighbours].
+    pub fn neighbours(&self, p: Point, neighbourhood: &'a Neighbourhood) -> impl Iterator<Item=(Point, u8)> + 'a {
+        let grid = *self;
+        neighbourhood.around(p).filter_map(move |n| {
+            let n = if neighbourhood.wraps() { wrap_into(grid.width, grid.height, n)? } else { n };
+            grid.get(n).map(|value| (n, value))
+        })
+    }
+}
+
+impl From<ByteGrid<'_>> for Grid<u8> {
+    fn from(grid: ByteGrid<'_>) -> Self {
+        Grid::from_fn(grid.width, grid.height, |p| grid.data[p.y as usize * grid.stride + p.x as usize])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn line_endings() {
+        for input in ["ab.\n.cd", "ab.\n.cd\n", "ab.\r\n.cd", "ab.\r\n.cd\r\n"] {
+            let grid = ByteGrid::new(input.as_bytes()).unwrap();
+            assert_eq!((grid.width(), grid.height()), (3, 2), "{:?}", input);
+            assert_eq!(grid.get(Point::new(2, 1)), Some(b'd'), "{:?}", input);
+            assert_eq!(grid.get(Point::new(3, 0)), None);
+            assert_eq!(grid.row(1), Some(&b".cd"[..]));
+            assert_eq!(grid.rows().collect::<Vec<_>>(), [b"ab.", b".cd"]);
+            assert_eq!(Grid::from(grid), "ab.\n.cd".parse::<Grid<u8>>().unwrap());
+        }
+        assert!(ByteGrid::new(b"").unwrap().is_empty());
+        assert!(ByteGrid::new(b"\n").unwrap().is_empty());
+    }
+
+    #[test]
+    fn invalid_layout() {
+        let err = ByteGrid::new(b"abc\nab\nabc\n").err().unwrap();
+        assert_eq!(err.line(), Some(2));
+        assert_eq!(err.message(), "Inconsistent line lengths. Expected 3 columns, found 2.");
+        let err = ByteGrid::new(b"abc\r\nabc\nabc").err().unwrap();
+        assert_eq!(err.line(), Some(2));
+        assert_eq!(err.message(), "Inconsistent line endings.");
+        assert!(ByteGrid::new(b"abc\n\nabc").is_err());
+        let err = ByteGrid::new("abc\na€c".as_bytes()).err().unwrap();
+        assert_eq!((err.line(), err.column(), err.span()), (Some(2), Some(2), 1..4));
+        let err = ByteGrid::new(b"abc\na\xffc").err().unwrap();
+        assert_eq!((err.line(), err.column()), (Some(2), Some(2)));
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = ByteGrid::new(b"abc\r\ndef\r\n").unwrap();
+        let neighbours: Vec<_> = grid.neighbours(Point::new(0, 0), &Neighbourhood::ALL).collect();
+        assert_eq!(neighbours, [(Point::new(1, 0), b'b'), (Point::new(1, 1), b'e'), (Point::new(0, 1), b'd')]);
+    }
+}
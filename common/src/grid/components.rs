@@ -0,0 +1,172 @@
+use std::collections::VecDeque;
+use super::{Grid, Neighbourhood, Point, Rect};
+
+/// A group of cells that are connected to each other through a neighbourhood.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Component {
+    label: usize,
+    members: Vec<Point>,
+    bounds: Rect,
+}
+
+impl Component {
+
+    /// The label of this component in [Components::labels].
+    pub fn label(&self) -> usize {
+        self.label
+    }
+
+    /// The positions of all cells in the component, in the order the flood fill reached them.
+    pub fn members(&self) -> &[Point] {
+        &self.members
+    }
+
+    /// The smallest rectangle containing all members.
+    pub fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    pub fn size(&self) -> usize {
+        self.members.len()
+    }
+}
+
+/// The result of labelling all connected components of a grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Components {
+    labels: Grid<Option<usize>>,
+    components: Vec<Component>,
+}
+
+impl Components {
+
+    /// For every cell the label of the component it belongs to, if any.
+    pub fn labels(&self) -> &Grid<Option<usize>> {
+        &self.labels
+    }
+
+    /// The component containing a position.
+    pub fn at(&self, p: Point) -> Option<&Component> {
+        let label = (*self.labels.get(p)?)?;
+        self.components.get(label)
+    }
+
+    /// The component with a label.
+    pub fn get(&self, label: usize) -> Option<&Component> {
+        self.components.get(label)
+    }
+
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    /// All components, ordered by label. Labels are assigned in reading order of the first cell.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item=&Component> {
+        self.components.iter()
+    }
+}
+
+impl<T> Grid<T> {
+
+    /// All positions connected to `start` through cells accepted by `joins`, starting with `start`
+    /// itself. Empty if `start` is not accepted.
+    pub fn flood_fill(&self, start: Point, neighbourhood: &Neighbourhood,
+                      joins: impl Fn(Point, &T) -> bool) -> Vec<Point> {
+        let mut seen = Grid::new(self.width, self.height, false);
+        self.fill(start, neighbourhood, &joins, &mut seen)
+    }
+
+    /// Split the cells accepted by `joins` into connected components.
+    pub fn components(&self, neighbourhood: &Neighbourhood, joins: impl Fn(Point, &T) -> bool) -> Components {
+        let mut seen = Grid::new(self.width, self.height, false);
+        let mut labels = Grid::new(self.width, self.height, None);
+        let mut components = Vec::new();
+
+        for p in self.points() {
+            if seen[p] {
+                continue;
+            }
+            let members = self.fill(p, neighbourhood, &joins, &mut seen);
+            let Some(&first) = members.first() else {
+                continue;
+            };
+            let label = components.len();
+            let mut bounds = Rect::point(first);
+            for &member in &members {
+                labels[member] = Some(label);
+                bounds.extend(member);
+            }
+            components.push(Component { label, members, bounds });
+        }
+        Components { labels, components }
+    }
+
+    fn fill(&self, start: Point, neighbourhood: &Neighbourhood, joins: &impl Fn(Point, &T) -> bool,
+            seen: &mut Grid<bool>) -> Vec<Point> {
+        let mut members = Vec::new();
+        match self.get(start) {
+            Some(value) if !seen[start] && joins(start, value) => seen[start] = true,
+            _ => return members,
+        }
+
+        let mut queue = VecDeque::from([start]);
+        while let Some(p) = queue.pop_front() {
+            members.push(p);
+            for (n, value) in self.neighbours(p, neighbourhood) {
+                if !seen[n] && joins(n, value) {
+                    seen[n] = true;
+                    queue.push_back(n);
+                }
+            }
+        }
+        members
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Input;
+
+    const MAP: &str = "
+        ##..#
+        #...#
+        ..#..
+        .#..#
+    ";
+
+    #[test]
+    fn flood_fill() {
+        let grid: Grid<char> = Input::new(MAP).text().parse().unwrap();
+        let walls = |_, c: &char| *c == '#';
+        assert_eq!(grid.flood_fill(Point::new(0, 0), &Neighbourhood::ORTHOGONAL, walls),
+                   [Point::new(0, 0), Point::new(1, 0), Point::new(0, 1)]);
+        assert_eq!(grid.flood_fill(Point::new(2, 0), &Neighbourhood::ORTHOGONAL, walls), []);
+        assert_eq!(grid.flood_fill(Point::new(2, 2), &Neighbourhood::ORTHOGONAL, walls).len(), 1);
+        assert_eq!(grid.flood_fill(Point::new(2, 2), &Neighbourhood::ALL, walls).len(), 2);
+    }
+
+    #[test]
+    fn labels_components() {
+        let grid: Grid<char> = Input::new(MAP).text().parse().unwrap();
+        let components = grid.components(&Neighbourhood::ORTHOGONAL, |_, c| *c == '#');
+        assert_eq!(components.len(), 5);
+        let sizes: Vec<_> = components.iter().map(Component::size).collect();
+        assert_eq!(sizes, [3, 2, 1, 1, 1]);
+
+        let right = components.at(Point::new(4, 1)).unwrap();
+        assert_eq!(right.label(), 1);
+        assert_eq!(right.bounds(), Rect::new(Point::new(4, 0), Point::new(4, 1)));
+        assert_eq!(components.labels()[Point::new(2, 2)], Some(2));
+        assert_eq!(components.labels()[Point::new(2, 0)], None);
+        assert!(components.at(Point::new(2, 0)).is_none());
+
+        let diagonal = grid.components(&Neighbourhood::ALL, |_, c| *c == '#');
+        assert_eq!(diagonal.len(), 4);
+        assert_eq!(diagonal.get(2).unwrap().bounds(), Rect::new(Point::new(1, 2), Point::new(2, 3)));
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Write as _};
+use std::fs::File;
+use std::io::{self, BufWriter, ErrorKind};
+use std::path::Path;
+use super::{Grid, Point};
+use super::render::Color;
+
+type ColorFn<'a, T> = Box<dyn Fn(Point, &T) -> Color + 'a>;
+
+/// Exports a grid as an SVG or PNG image, one square per cell.
+///
+/// Only available with the `image` feature of `common`, so that the solutions do not build the PNG encoder.
+pub struct ImageExport<'a, T> {
+    grid: &'a Grid<T>,
+    color: ColorFn<'a, T>,
+    highlights: HashMap<Point, Color>,
+    cell_size: u32,
+    text: bool,
+}
+
+impl<'a, T: Display> ImageExport<'a, T> {
+
+    /// Export a grid with cells coloured by `color`.
+    pub fn new(grid: &'a Grid<T>, color: impl Fn(Point, &T) -> Color + 'a) -> Self {
+        ImageExport {
+            grid,
+            color: Box::new(color),
+            highlights: HashMap::new(),
+            cell_size: 8,
+            text: false,
+        }
+    }
+
+    /// Colour a set of cells differently than the mapping does, e.g. to show what a solution
+    /// counted. Later highlights win.
+    pub fn highlight(mut self, points: impl IntoIterator<Item=Point>, color: Color) -> Self {
+        self.highlights.extend(points.into_iter().map(|p| (p, color)));
+        self
+    }
+
+    /// The width and height of a cell in pixels. Defaults to 8.
+    pub fn cell_size(mut self, size: u32) -> Self {
+        self.cell_size = size.max(1);
+        self
+    }
+
+    /// Also write the value of every cell on top of it. Only SVG supports this.
+    pub fn text(mut self, text: bool) -> Self {
+        self.text = text;
+        self
+    }
+
+    fn color_of(&self, p: Point, value: &T) -> Color {
+        self.highlights.get(&p).copied().unwrap_or_else(|| (self.color)(p, value))
+    }
+
+    pub fn to_svg(&self) -> String {
+        let size = self.cell_size as usize;
+        let mut svg = String::new();
+        let _ = writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" font-family="monospace" font-size="{}">"#,
+                         self.grid.width() * size, self.grid.height() * size, size * 3 / 4);
+        for (p, value) in self.grid.iter() {
+            let (r, g, b) = self.color_of(p, value).rgb();
+            let (x, y) = (p.x as usize * size, p.y as usize * size);
+            let _ = writeln!(svg, r##"<rect x="{}" y="{}" width="{}" height="{}" fill="#{:02x}{:02x}{:02x}"/>"##,
+                             x, y, size, size, r, g, b);
+            if self.text {
+                let _ = writeln!(svg, r#"<text x="{}" y="{}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+                                 x + size / 2, y + size / 2, escape(&value.to_string()));
+            }
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Encode the grid as an RGB PNG.
+    pub fn to_png(&self) -> io::Result<Vec<u8>> {
+        let size = self.cell_size as usize;
+        let (width, height) = (self.grid.width() * size, self.grid.height() * size);
+        let mut pixels = vec![0; width * height * 3];
+        for (p, value) in self.grid.iter() {
+            let (r, g, b) = self.color_of(p, value).rgb();
+            for dy in 0..size {
+                let row_start = ((p.y as usize * size + dy) * width + p.x as usize * size) * 3;
+                for pixel in pixels[row_start..row_start + size * 3].chunks_exact_mut(3) {
+                    pixel.copy_from_slice(&[r, g, b]);
+                }
+            }
+        }
+
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, width as u32, height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()?.write_image_data(&pixels)?;
+        Ok(png)
+    }
+
+    /// Write the image to a file, as SVG or PNG depending on the extension.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let extension = path.extension().and_then(|e| e.to_str()).map(str::to_ascii_lowercase);
+        let data = match extension.as_deref() {
+            Some("svg") => self.to_svg().into_bytes(),
+            Some("png") => self.to_png()?,
+            _ => return Err(io::Error::new(ErrorKind::InvalidInput,
+                                           format!("Unknown image format of {}. Expected .svg or .png.", path.display()))),
+        };
+        io::Write::write_all(&mut BufWriter::new(File::create(path)?), &data)
+    }
+}
+
+/// Escape text for use in XML.
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn export(grid: &Grid<char>) -> ImageExport<'_, char> {
+        ImageExport::new(grid, |_, c| match c {
+            '.' => Color::Black,
+            '0'..='9' => Color::White,
+            _ => Color::Rgb(255, 0, 0),
+        })
+    }
+
+    #[test]
+    fn svg() {
+        let grid: Grid<char> = "1.\n<*".parse().unwrap();
+        let svg = export(&grid).cell_size(10).highlight([Point::new(1, 1)], Color::Rgb(0, 0, 255)).text(true).to_svg();
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="20""#));
+        assert!(svg.contains(r##"<rect x="0" y="0" width="10" height="10" fill="#e5e5e5"/>"##));
+        assert!(svg.contains(r##"<rect x="0" y="10" width="10" height="10" fill="#ff0000"/>"##));
+        assert!(svg.contains(r##"<rect x="10" y="10" width="10" height="10" fill="#0000ff"/>"##));
+        assert!(svg.contains(">&lt;</text>"));
+        assert_eq!(svg.matches("<rect").count(), 4);
+    }
+
+    #[test]
+    fn png() {
+        let grid: Grid<char> = "1.\n.*".parse().unwrap();
+        let png = export(&grid).cell_size(3).to_png().unwrap();
+
+        let mut reader = png::Decoder::new(png.as_slice()).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (6, 6));
+        let pixel = |x: usize, y: usize| &pixels[(y * 6 + x) * 3..][..3];
+        assert_eq!(pixel(2, 2), [229, 229, 229]);
+        assert_eq!(pixel(3, 2), [0, 0, 0]);
+        assert_eq!(pixel(5, 5), [255, 0, 0]);
+    }
+}
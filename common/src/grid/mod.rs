@@ -0,0 +1,446 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Index, IndexMut, Mul, Neg, Sub};
+use std::str::FromStr;
+use crate::ParseError;
+
+mod bytes;
+mod components;
+#[cfg(feature = "image")]
+pub mod image;
+mod neighbours;
+pub mod render;
+mod runs;
+mod sparse;
+mod view;
+
+pub use bytes::ByteGrid;
+pub use components::{Component, Components};
+pub use neighbours::Neighbourhood;
+pub use runs::{Axis, Run, Runs};
+pub use sparse::{Coordinate, SparseGrid};
+pub use view::GridView;
+
+/// A position on a grid. `x` is the column and `y` the row, growing to the right and down.
+///
+/// Coordinates are signed, so that offsets can be added without checking for underflow first. Positions
+/// outside of a grid simply have no cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const UP: Point = Point::new(0, -1);
+    pub const RIGHT: Point = Point::new(1, 0);
+    pub const DOWN: Point = Point::new(0, 1);
+    pub const LEFT: Point = Point::new(-1, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Point { x, y }
+    }
+
+    /// The manhattan distance between two points.
+    pub fn manhattan(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Self::Output {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Self::Output {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Self::Output {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+/// A position in three dimensions, for sparse grids of voxels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    /// The six neighbours sharing a face.
+    pub const ORTHOGONAL: [Point3; 6] = [
+        Point3::new(1, 0, 0),
+        Point3::new(-1, 0, 0),
+        Point3::new(0, 1, 0),
+        Point3::new(0, -1, 0),
+        Point3::new(0, 0, 1),
+        Point3::new(0, 0, -1),
+    ];
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Point3 { x, y, z }
+    }
+
+    /// The manhattan distance between two points.
+    pub fn manhattan(self, other: Point3) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+}
+
+impl Display for Point3 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+impl Add for Point3 {
+    type Output = Point3;
+
+    fn add(self, rhs: Point3) -> Self::Output {
+        Point3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Point3;
+
+    fn sub(self, rhs: Point3) -> Self::Output {
+        Point3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+/// An axis aligned rectangle of points, with both corners inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Rect {
+
+    /// The rectangle spanned by two corners, in any order.
+    pub fn new(a: Point, b: Point) -> Self {
+        Rect {
+            min: Point::new(a.x.min(b.x), a.y.min(b.y)),
+            max: Point::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    /// The rectangle containing just one point.
+    pub fn point(p: Point) -> Self {
+        Rect { min: p, max: p }
+    }
+
+    /// Grow the rectangle so that it contains `p`.
+    pub fn extend(&mut self, p: Point) {
+        *self = Rect::new(Point::new(self.min.x.min(p.x), self.min.y.min(p.y)),
+                          Point::new(self.max.x.max(p.x), self.max.y.max(p.y)));
+    }
+
+    pub fn width(&self) -> u64 {
+        self.min.x.abs_diff(self.max.x) + 1
+    }
+
+    pub fn height(&self) -> u64 {
+        self.min.y.abs_diff(self.max.y) + 1
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+}
+
+/// Whether a position is inside of an area of `width` by `height` cells with its top left corner at the
+/// origin, the shape of every dense grid.
+fn in_bounds(width: usize, height: usize, p: Point) -> bool {
+    p.x >= 0 && p.y >= 0 && (p.x as usize) < width && (p.y as usize) < height
+}
+
+/// All positions of such an area in row-major order.
+fn area_points(width: usize, height: usize) -> impl Iterator<Item=Point> {
+    (0..height).flat_map(move |y| (0..width).map(move |x| Point::new(x as i64, y as i64)))
+}
+
+/// Move a position back into such an area, as if the area was a torus. `None` if the area is empty.
+fn wrap_into(width: usize, height: usize, p: Point) -> Option<Point> {
+    if width == 0 || height == 0 {
+        return None;
+    }
+    Some(Point::new(p.x.rem_euclid(width as i64), p.y.rem_euclid(height as i64)))
+}
+
+/// The error message for a row that is not as long as the first one.
+fn inconsistent_lengths(expected: usize, found: usize) -> String {
+    format!("Inconsistent line lengths. Expected {} columns, found {}.", expected, found)
+}
+
+/// A dense, rectangular grid of cells stored row by row in a single vector.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+
+    /// Create a grid from its cells in row-major order. Returns `None` if the number of cells does not
+    /// fit the width.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Option<Self> {
+        if width == 0 {
+            return cells.is_empty().then_some(Grid { width, height: 0, cells });
+        }
+        if !cells.len().is_multiple_of(width) {
+            return None;
+        }
+        Some(Grid {
+            width,
+            height: cells.len() / width,
+            cells,
+        })
+    }
+
+    /// Create a grid by calling `f` for every position.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point) -> T) -> Self {
+        let mut cells = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(f(Point::new(x as i64, y as i64)));
+            }
+        }
+        Grid { width, height, cells }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Whether the position is inside of the grid.
+    pub fn contains(&self, p: Point) -> bool {
+        in_bounds(self.width, self.height, p)
+    }
+
+    /// Index of a position into the row-major cell storage, if it is inside of the grid.
+    pub fn index_of(&self, p: Point) -> Option<usize> {
+        self.contains(p).then(|| p.y as usize * self.width + p.x as usize)
+    }
+
+    /// Position of an index into the row-major cell storage.
+    pub fn point_of(&self, idx: usize) -> Point {
+        Point::new((idx % self.width) as i64, (idx / self.width) as i64)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.index_of(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.index_of(p).map(|i| &mut self.cells[i])
+    }
+
+    /// The cells of a row, if it exists.
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    /// All rows from top to bottom.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item=&[T]> + ExactSizeIterator {
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    /// The cells of a column from top to bottom. Empty if the column does not exist.
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item=&T> + ExactSizeIterator {
+        let cells = if x < self.width { &self.cells[x..] } else { &[] };
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    /// All columns from left to right.
+    pub fn columns(&self) -> impl DoubleEndedIterator<Item=impl DoubleEndedIterator<Item=&T>> + ExactSizeIterator {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// All positions in row-major order.
+    pub fn points(&self) -> impl Iterator<Item=Point> {
+        area_points(self.width, self.height)
+    }
+
+    /// All cells with their positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item=(Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// The cells in row-major order.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// Create a grid of the same size with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Create a grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &Self::Output {
+        self.get(p).unwrap_or_else(|| panic!("{} is outside of the {}x{} grid", p, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(p).unwrap_or_else(|| panic!("{} is outside of the {}x{} grid", p, width, height))
+    }
+}
+
+/// Parse a grid with one character per cell and one line per row.
+///
+/// The text is taken as it is: only the line endings, `\n` or `\r\n`, are removed, so whitespace
+/// inside a row is a cell like any other. Blank lines before the first and after the last row are
+/// padding and skipped. All rows have to be of the same length. Indented test fixtures can be prepared
+/// with [Input::text](crate::Input::text).
+impl<T: TryFrom<char>> FromStr for Grid<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<&str> = s.lines().collect();
+        let is_padding = |line: &&str| line.trim().is_empty();
+        let first = lines.iter().position(|line| !is_padding(line)).unwrap_or(lines.len());
+        let last = lines.iter().rposition(|line| !is_padding(line)).map_or(first, |idx| idx + 1);
+
+        let mut width = None;
+        let mut cells = Vec::new();
+        for (idx, line) in lines[first..last].iter().enumerate() {
+            let number = first + idx + 1;
+            let mut row_len = 0;
+            for (offset, c) in line.char_indices() {
+                let cell = T::try_from(c).map_err(|_| {
+                    ParseError::at(line, &line[offset..offset + c.len_utf8()], format!("Invalid cell '{}'", c))
+                        .in_line(number, line, line)
+                })?;
+                cells.push(cell);
+                row_len += 1;
+            }
+            match width {
+                None => width = Some(row_len),
+                Some(w) if w != row_len => {
+                    return Err(ParseError::at(line, line, inconsistent_lengths(w, row_len)).in_line(number, line, line));
+                }
+                Some(_) => {}
+            }
+        }
+        Ok(Grid::from_vec(width.unwrap_or(0), cells).expect("All rows have the same width"))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_and_access() {
+        let grid: Grid<char> = "\nab.\nc.d\n".parse().unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(Point::new(2, 1)), Some(&'d'));
+        assert_eq!(grid.get(Point::new(-1, 0)), None);
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.row(1), Some(&['c', '.', 'd'][..]));
+        assert_eq!(grid.column(1).collect::<String>(), "b.");
+        assert_eq!(grid.columns().map(|c| c.collect::<String>()).collect::<Vec<_>>(), ["ac", "b.", ".d"]);
+        assert_eq!(grid.to_string(), "ab.\nc.d\n");
+    }
+
+    #[test]
+    fn get_mut() {
+        let mut grid = Grid::new(2, 2, 0u8);
+        *grid.get_mut(Point::new(1, 1)).unwrap() = 5;
+        grid[Point::new(0, 1)] += 1;
+        assert_eq!(grid.cells(), &[0, 0, 1, 5]);
+        assert!(grid.get_mut(Point::new(2, 0)).is_none());
+        assert_eq!(grid.point_of(3), Point::new(1, 1));
+    }
+
+    #[test]
+    fn whitespace_is_a_cell() {
+        let grid: Grid<char> = "\n \n a \r\n   \n b \n\n".parse().unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid.to_string(), " a \n   \n b \n");
+        let err = "ab \nab".parse::<Grid<char>>().unwrap_err();
+        assert_eq!(err.line(), Some(2));
+    }
+
+    #[test]
+    fn rejects_ragged_input() {
+        let err = "abc\nab\n".parse::<Grid<char>>().unwrap_err();
+        assert_eq!(err.line(), Some(2));
+    }
+
+    #[test]
+    fn byte_cells_reject_wide_chars() {
+        let err = "ab\na€\n".parse::<Grid<u8>>().unwrap_err();
+        assert_eq!((err.line(), err.column()), (Some(2), Some(2)));
+    }
+}
@@ -0,0 +1,143 @@
+use std::borrow::Cow;
+use super::{wrap_into, Grid, Point};
+
+/// Which cells count as neighbours of a cell, given as offsets from it.
+///
+/// The presets cover the common cases, [Neighbourhood::custom] takes any stencil. A wrapping neighbourhood
+/// treats the grid as a torus, so that neighbours leaving one edge come back in on the opposite edge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Neighbourhood {
+    offsets: Cow<'static, [Point]>,
+    wrap: bool,
+}
+
+impl Neighbourhood {
+
+    /// The four orthogonal neighbours, clockwise starting at the top.
+    pub const ORTHOGONAL: Neighbourhood = Neighbourhood {
+        offsets: Cow::Borrowed(&[Point::UP, Point::RIGHT, Point::DOWN, Point::LEFT]),
+        wrap: false,
+    };
+
+    /// The four diagonal neighbours, clockwise starting at the top right.
+    pub const DIAGONAL: Neighbourhood = Neighbourhood {
+        offsets: Cow::Borrowed(&[Point::new(1, -1), Point::new(1, 1), Point::new(-1, 1), Point::new(-1, -1)]),
+        wrap: false,
+    };
+
+    /// All eight surrounding cells, clockwise starting at the top left.
+    pub const ALL: Neighbourhood = Neighbourhood {
+        offsets: Cow::Borrowed(&[
+            Point::new(-1, -1),
+            Point::new(0, -1),
+            Point::new(1, -1),
+            Point::new(1, 0),
+            Point::new(1, 1),
+            Point::new(0, 1),
+            Point::new(-1, 1),
+            Point::new(-1, 0),
+        ]),
+        wrap: false,
+    };
+
+    /// A neighbourhood made of arbitrary offsets, visited in the given order.
+    pub fn custom(offsets: impl Into<Vec<Point>>) -> Self {
+        Neighbourhood {
+            offsets: Cow::Owned(offsets.into()),
+            wrap: false,
+        }
+    }
+
+    /// The same neighbourhood, but wrapping around the edges of the grid.
+    pub fn wrapping(mut self) -> Self {
+        self.wrap = true;
+        self
+    }
+
+    pub fn offsets(&self) -> &[Point] {
+        &self.offsets
+    }
+
+    pub fn wraps(&self) -> bool {
+        self.wrap
+    }
+
+    /// The neighbour positions of `p`, without any bounds.
+    pub fn around(&self, p: Point) -> impl Iterator<Item=Point> + '_ {
+        self.offsets.iter().map(move |offset| p + *offset)
+    }
+}
+
+impl<T> Grid<T> {
+
+    /// Move a position that may be outside of the grid back into it, as if the grid was a torus.
+    /// Returns `None` for an empty grid.
+    pub fn wrap(&self, p: Point) -> Option<Point> {
+        wrap_into(self.width(), self.height(), p)
+    }
+
+    /// The neighbours of a position with their values. Positions outside of the grid are skipped, unless
+    /// the neighbourhood wraps.
+    pub fn neighbours<'a>(&'a self, p: Point, neighbourhood: &'a Neighbourhood) -> impl Iterator<Item=(Point, &'a T)> + 'a {
+        neighbourhood.around(p).filter_map(move |n| {
+            let n = if neighbourhood.wrap { self.wrap(n)? } else { n };
+            self.get(n).map(|value| (n, value))
+        })
+    }
+
+    /// The positions of the neighbours that can be entered, according to `passable`.
+    pub fn passable_neighbours<'a>(&'a self, p: Point, neighbourhood: &'a Neighbourhood,
+                                   passable: impl Fn(Point, &T) -> bool + 'a) -> impl Iterator<Item=Point> + 'a {
+        self.neighbours(p, neighbourhood)
+            .filter(move |(n, value)| passable(*n, value))
+            .map(|(n, _)| n)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        "abc\ndef\nghi".parse().unwrap()
+    }
+
+    fn values<'a>(it: impl Iterator<Item=(Point, &'a char)>) -> String {
+        it.map(|(_, c)| *c).collect()
+    }
+
+    #[test]
+    fn presets() {
+        let grid = grid();
+        let center = Point::new(1, 1);
+        assert_eq!(values(grid.neighbours(center, &Neighbourhood::ORTHOGONAL)), "bfhd");
+        assert_eq!(values(grid.neighbours(center, &Neighbourhood::DIAGONAL)), "ciga");
+        assert_eq!(values(grid.neighbours(center, &Neighbourhood::ALL)), "abcfihgd");
+    }
+
+    #[test]
+    fn skips_outside() {
+        let grid = grid();
+        let neighbours: Vec<_> = grid.neighbours(Point::new(0, 0), &Neighbourhood::ALL).collect();
+        assert_eq!(neighbours, [(Point::new(1, 0), &'b'), (Point::new(1, 1), &'e'), (Point::new(0, 1), &'d')]);
+    }
+
+    #[test]
+    fn wrapping() {
+        let grid = grid();
+        let hood = Neighbourhood::ORTHOGONAL.wrapping();
+        let neighbours: Vec<_> = grid.neighbours(Point::new(0, 0), &hood).collect();
+        assert_eq!(neighbours, [
+            (Point::new(0, 2), &'g'),
+            (Point::new(1, 0), &'b'),
+            (Point::new(0, 1), &'d'),
+            (Point::new(2, 0), &'c'),
+        ]);
+    }
+
+    #[test]
+    fn custom_stencil() {
+        let knight = Neighbourhood::custom([Point::new(1, 2), Point::new(2, 1), Point::new(-1, 2)]);
+        assert_eq!(values(grid().neighbours(Point::new(0, 0), &knight)), "hf");
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Write as _};
+use std::io::{IsTerminal, Write};
+use super::{Grid, Point};
+
+/// A colour for rendering cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+
+    /// The red, green and blue components, using the usual terminal palette for named colours.
+    pub fn rgb(self) -> (u8, u8, u8) {
+        match self {
+            Color::Black => (0, 0, 0),
+            Color::Red => (205, 49, 49),
+            Color::Green => (13, 188, 121),
+            Color::Yellow => (229, 229, 16),
+            Color::Blue => (36, 114, 200),
+            Color::Magenta => (188, 63, 188),
+            Color::Cyan => (17, 168, 205),
+            Color::White => (229, 229, 229),
+            Color::Rgb(r, g, b) => (r, g, b),
+        }
+    }
+
+    /// The SGR parameters selecting this colour, `base` being 30 for the foreground and 40 for the
+    /// background.
+    fn sgr(self, base: u8) -> String {
+        match self {
+            Color::Black => base.to_string(),
+            Color::Red => (base + 1).to_string(),
+            Color::Green => (base + 2).to_string(),
+            Color::Yellow => (base + 3).to_string(),
+            Color::Blue => (base + 4).to_string(),
+            Color::Magenta => (base + 5).to_string(),
+            Color::Cyan => (base + 6).to_string(),
+            Color::White => (base + 7).to_string(),
+            Color::Rgb(r, g, b) => format!("{};2;{};{};{}", base + 8, r, g, b),
+        }
+    }
+}
+
+/// How a cell is drawn. Unset attributes are taken from the style below, see [Style::over].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Style {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub bold: bool,
+}
+
+impl Style {
+
+    pub fn new() -> Self {
+        Style::default()
+    }
+
+    pub fn fg(self, color: Color) -> Self {
+        Style { fg: Some(color), ..self }
+    }
+
+    pub fn bg(self, color: Color) -> Self {
+        Style { bg: Some(color), ..self }
+    }
+
+    pub fn bold(self) -> Self {
+        Style { bold: true, ..self }
+    }
+
+    /// This style drawn on top of `base`.
+    pub fn over(self, base: Style) -> Self {
+        Style {
+            fg: self.fg.or(base.fg),
+            bg: self.bg.or(base.bg),
+            bold: self.bold || base.bold,
+        }
+    }
+
+    /// The escape sequence switching to this style, empty for the default style.
+    fn escape(self) -> String {
+        let mut params = Vec::new();
+        if self.bold {
+            params.push("1".to_string());
+        }
+        params.extend(self.fg.map(|c| c.sgr(30)));
+        params.extend(self.bg.map(|c| c.sgr(40)));
+        if params.is_empty() {
+            String::new()
+        } else {
+            format!("\x1b[{}m", params.join(";"))
+        }
+    }
+}
+
+type StyleFn<'a, T> = Box<dyn Fn(Point, &T) -> Style + 'a>;
+
+/// Draws a grid as text, optionally with colours, highlighted cells and labels.
+///
+/// ```
+/// # use common::grid::{Grid, Point};
+/// # use common::grid::render::{Color, Renderer, Style};
+/// let grid: Grid<char> = "..#\n#..".parse().unwrap();
+/// let text = Renderer::new(&grid)
+///     .style(|_, c| if *c == '#' { Style::new().fg(Color::Blue) } else { Style::new() })
+///     .highlight([Point::new(0, 0), Point::new(1, 0)], Style::new().bg(Color::Yellow))
+///     .label(Point::new(1, 1), 7)
+///     .colored(false)
+///     .render();
+/// assert_eq!(text, "..#\n#7.\n");
+/// ```
+pub struct Renderer<'a, T> {
+    grid: &'a Grid<T>,
+    style: Option<StyleFn<'a, T>>,
+    highlights: HashMap<Point, Style>,
+    labels: Vec<(Point, String)>,
+    colored: Option<bool>,
+}
+
+impl<'a, T: Display> Renderer<'a, T> {
+
+    pub fn new(grid: &'a Grid<T>) -> Self {
+        Renderer {
+            grid,
+            style: None,
+            highlights: HashMap::new(),
+            labels: Vec::new(),
+            colored: None,
+        }
+    }
+
+    /// Style every cell according to its position and value.
+    pub fn style(mut self, style: impl Fn(Point, &T) -> Style + 'a) -> Self {
+        self.style = Some(Box::new(style));
+        self
+    }
+
+    /// Draw a set of cells with a style on top of their own. Later highlights are drawn on top of
+    /// earlier ones.
+    pub fn highlight(mut self, points: impl IntoIterator<Item=Point>, style: Style) -> Self {
+        for p in points {
+            let below = self.highlights.get(&p).copied().unwrap_or_default();
+            self.highlights.insert(p, style.over(below));
+        }
+        self
+    }
+
+    /// Write a label over the cells starting at `p`, one character per cell, cut off at the end of
+    /// the row. Later labels are written on top of earlier ones.
+    pub fn label(mut self, p: Point, label: impl Display) -> Self {
+        self.labels.push((p, label.to_string()));
+        self
+    }
+
+    /// Write several labels, e.g. component ids or the steps of a path.
+    pub fn labels<L: Display>(self, labels: impl IntoIterator<Item=(Point, L)>) -> Self {
+        labels.into_iter().fold(self, |renderer, (p, label)| renderer.label(p, label))
+    }
+
+    /// Force colours on or off. By default they are used when printing to a terminal and the
+    /// `NO_COLOR` environment variable is not set.
+    pub fn colored(mut self, colored: bool) -> Self {
+        self.colored = Some(colored);
+        self
+    }
+
+    /// Render the grid with one line per row, with colours only if they were turned on with
+    /// [Renderer::colored].
+    pub fn render(&self) -> String {
+        self.render_with(self.colored.unwrap_or(false))
+    }
+
+    /// Print the grid to stdout.
+    pub fn print(&self) -> std::io::Result<()> {
+        let stdout = std::io::stdout();
+        let colored = self.colored
+            .unwrap_or_else(|| stdout.is_terminal() && std::env::var_os("NO_COLOR").is_none());
+        stdout.lock().write_all(self.render_with(colored).as_bytes())
+    }
+
+    fn render_with(&self, colored: bool) -> String {
+        let mut text: Vec<String> = self.grid.cells().iter().map(|cell| cell.to_string()).collect();
+        for (p, label) in &self.labels {
+            for (i, c) in label.chars().enumerate() {
+                if let Some(idx) = self.grid.index_of(*p + Point::new(i as i64, 0)) {
+                    text[idx] = c.to_string();
+                }
+            }
+        }
+
+        let mut out = String::new();
+        for ((p, value), cell) in self.grid.iter().zip(&text) {
+            let style = self.style.as_ref().map_or_else(Style::default, |style| style(p, value));
+            let style = self.highlights.get(&p).map_or(style, |highlight| highlight.over(style));
+            let escape = style.escape();
+            if colored && !escape.is_empty() {
+                let _ = write!(out, "{}{}\x1b[0m", escape, cell);
+            } else {
+                out.push_str(cell);
+            }
+            if p.x as usize + 1 == self.grid.width() {
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn plain_text() {
+        let grid: Grid<char> = "abc\ndef".parse().unwrap();
+        let text = Renderer::new(&grid)
+            .highlight([Point::new(0, 0)], Style::new().fg(Color::Red))
+            .labels([(Point::new(1, 0), 42), (Point::new(-1, 1), 9)])
+            .render();
+        assert_eq!(text, "a42\ndef\n");
+    }
+
+    #[test]
+    fn colors() {
+        let grid: Grid<char> = "ab\n*1".parse().unwrap();
+        let text = Renderer::new(&grid)
+            .style(|_, c| if *c == '*' { Style::new().fg(Color::Yellow) } else { Style::new() })
+            .highlight([Point::new(0, 1), Point::new(1, 1)], Style::new().bg(Color::Rgb(1, 2, 3)).bold())
+            .colored(true)
+            .render();
+        assert_eq!(text, "ab\n\x1b[1;33;48;2;1;2;3m*\x1b[0m\x1b[1;48;2;1;2;3m1\x1b[0m\n");
+    }
+}
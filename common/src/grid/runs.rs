@@ -0,0 +1,248 @@
+use std::fmt::Display;
+use std::str::FromStr;
+use crate::ParseError;
+use super::{Grid, Point};
+
+/// The kind of lines runs are searched along.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    /// Left to right, top to bottom.
+    Rows,
+    /// Top to bottom, left to right.
+    Columns,
+    /// Down and to the right, starting with the bottom left corner.
+    Diagonals,
+    /// Down and to the left, starting with the top left corner.
+    AntiDiagonals,
+}
+
+impl Axis {
+
+    /// The offset from one cell of a line to the next.
+    pub fn step(self) -> Point {
+        match self {
+            Axis::Rows => Point::RIGHT,
+            Axis::Columns => Point::DOWN,
+            Axis::Diagonals => Point::new(1, 1),
+            Axis::AntiDiagonals => Point::new(-1, 1),
+        }
+    }
+
+    /// The first cell of every line of a grid.
+    fn starts(self, width: usize, height: usize) -> Vec<Point> {
+        let (width, height) = (width as i64, height as i64);
+        match self {
+            Axis::Rows => (0..height).map(|y| Point::new(0, y)).collect(),
+            Axis::Columns => (0..width).map(|x| Point::new(x, 0)).collect(),
+            Axis::Diagonals => (0..height).rev().map(|y| Point::new(0, y))
+                .chain((1..width).map(|x| Point::new(x, 0)))
+                .collect(),
+            Axis::AntiDiagonals => (0..width).map(|x| Point::new(x, 0))
+                .chain((1..height).map(|y| Point::new(width - 1, y)))
+                .collect(),
+        }
+    }
+}
+
+/// A maximal sequence of consecutive cells along a line that all match a predicate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Run {
+    /// The first cell.
+    pub start: Point,
+    /// The offset from one cell to the next.
+    pub step: Point,
+    /// The number of cells, never 0.
+    pub len: usize,
+}
+
+impl Run {
+
+    /// The last cell.
+    pub fn end(&self) -> Point {
+        self.start + self.step * (self.len as i64 - 1)
+    }
+
+    pub fn points(&self) -> impl Iterator<Item=Point> {
+        let Run { start, step, len } = *self;
+        (0..len as i64).map(move |i| start + step * i)
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.points().any(|q| q == p)
+    }
+
+    /// The values of the cells of the run.
+    pub fn cells<'a, T>(&self, grid: &'a Grid<T>) -> impl Iterator<Item=&'a T> {
+        self.points().map(|p| &grid[p])
+    }
+}
+
+/// Iterator over the runs of a grid, see [Grid::runs].
+pub struct Runs<'a, T, F> {
+    grid: &'a Grid<T>,
+    step: Point,
+    starts: std::vec::IntoIter<Point>,
+    next: Option<Point>,
+    matches: F,
+}
+
+impl<T, F: Fn(&T) -> bool> Iterator for Runs<'_, T, F> {
+    type Item = Run;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let mut p = match self.next {
+                Some(p) => p,
+                None => self.starts.next()?,
+            };
+
+            // Seek for the next run to start
+            while self.grid.get(p).is_some_and(|v| !(self.matches)(v)) {
+                p = p + self.step;
+            }
+
+            // End of the line
+            if !self.grid.contains(p) {
+                self.next = None;
+                continue;
+            }
+
+            // Seek until the end of the run
+            let start = p;
+            let mut len = 0;
+            while self.grid.get(p).is_some_and(&self.matches) {
+                p = p + self.step;
+                len += 1;
+            }
+            self.next = Some(p);
+            return Some(Run { start, step: self.step, len });
+        }
+    }
+}
+
+impl<T> Grid<T> {
+
+    /// All maximal runs of cells accepted by `matches` along the lines of an axis.
+    pub fn runs<F: Fn(&T) -> bool>(&self, axis: Axis, matches: F) -> Runs<'_, T, F> {
+        Runs {
+            grid: self,
+            step: axis.step(),
+            starts: axis.starts(self.width, self.height).into_iter(),
+            next: None,
+            matches,
+        }
+    }
+}
+
+impl Grid<char> {
+
+    /// The characters of a run.
+    pub fn run_text(&self, run: &Run) -> String {
+        run.cells(self).collect()
+    }
+
+    /// All numbers along the lines of an axis, as maximal runs of ASCII digits.
+    ///
+    /// A number that does not fit into `N` is an error relative to the row its run starts in, so that
+    /// the caller, which knows where the row came from, can attach the line with [ParseError::in_line].
+    pub fn numbers<N>(&self, axis: Axis) -> impl Iterator<Item=(Run, Result<N, ParseError>)> + '_
+        where N: FromStr,
+              N::Err: Display
+    {
+        self.runs(axis, char::is_ascii_digit)
+            .map(|run| self.parse_run(run))
+    }
+
+    /// Like [Grid::numbers], but a `-` or `+` right before the digits is part of the number.
+    pub fn signed_numbers<N>(&self, axis: Axis) -> impl Iterator<Item=(Run, Result<N, ParseError>)> + '_
+        where N: FromStr,
+              N::Err: Display
+    {
+        self.runs(axis, char::is_ascii_digit)
+            .map(|run| {
+                let before = run.start - run.step;
+                match self.get(before) {
+                    Some('-' | '+') => Run { start: before, step: run.step, len: run.len + 1 },
+                    _ => run,
+                }
+            })
+            .map(|run| self.parse_run(run))
+    }
+
+    /// Parse the text of a run. Errors point at the part of the run in the row it starts in.
+    fn parse_run<N>(&self, run: Run) -> (Run, Result<N, ParseError>)
+        where N: FromStr,
+              N::Err: Display
+    {
+        let text = self.run_text(&run);
+        let number = N::from_str(&text)
+            .map_err(|e| {
+                let y = run.start.y as usize;
+                let row: String = self.row(y).unwrap_or_default().iter().collect();
+                let in_row = run.points().take_while(|p| p.y == run.start.y).count();
+                let first = row.char_indices().nth(run.start.x as usize).map_or(0, |(idx, _)| idx);
+                let last = row.char_indices().nth(run.start.x as usize + in_row).map_or(row.len(), |(idx, _)| idx);
+                let (first, last) = (first.min(last), first.max(last));
+                ParseError::new(format!("Invalid number {}: {}", text, e), first..last)
+            });
+        (run, number)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Input;
+
+    const NUMBERS: &str = "
+        12.-4
+        3..5.
+        +7-..
+    ";
+
+    fn texts(grid: &Grid<char>, axis: Axis) -> Vec<String> {
+        grid.runs(axis, |c| *c != '.').map(|run| grid.run_text(&run)).collect()
+    }
+
+    #[test]
+    fn runs_along_axes() {
+        let grid: Grid<char> = Input::new(NUMBERS).text().parse().unwrap();
+        assert_eq!(texts(&grid, Axis::Rows), ["12", "-4", "3", "5", "+7-"]);
+        assert_eq!(texts(&grid, Axis::Columns), ["13+", "2", "7", "-", "-5", "4"]);
+        assert_eq!(texts(&grid, Axis::Diagonals), ["+", "37", "1", "-", "2", "5", "-", "4"]);
+        assert_eq!(texts(&grid, Axis::AntiDiagonals), ["1", "23", "+", "-", "7", "45-"]);
+
+        let run = grid.runs(Axis::Columns, |c| *c != '.').next().unwrap();
+        assert_eq!(run, Run { start: Point::new(0, 0), step: Point::DOWN, len: 3 });
+        assert_eq!(run.end(), Point::new(0, 2));
+        assert!(run.contains(Point::new(0, 1)));
+    }
+
+    #[test]
+    fn parses_numbers() {
+        let grid: Grid<char> = Input::new(NUMBERS).text().parse().unwrap();
+        let unsigned: Vec<u32> = grid.numbers(Axis::Rows).map(|(_, n)| n.unwrap()).collect();
+        assert_eq!(unsigned, [12, 4, 3, 5, 7]);
+        let signed: Vec<(Point, i32)> = grid.signed_numbers(Axis::Rows)
+            .map(|(run, n)| (run.start, n.unwrap()))
+            .collect();
+        assert_eq!(signed, [
+            (Point::new(0, 0), 12),
+            (Point::new(3, 0), -4),
+            (Point::new(0, 1), 3),
+            (Point::new(3, 1), 5),
+            (Point::new(0, 2), 7),
+        ]);
+        let columns: Vec<i32> = grid.signed_numbers(Axis::Columns).map(|(_, n)| n.unwrap()).collect();
+        assert_eq!(columns, [13, 2, 7, -5, 4]);
+    }
+
+    #[test]
+    fn error_points_at_run() {
+        let grid: Grid<char> = ".......\n.€300..".parse().unwrap();
+        let (run, err) = grid.numbers::<u8>(Axis::Rows).next().unwrap();
+        let err = err.unwrap_err();
+        assert_eq!(run.start, Point::new(2, 1));
+        assert_eq!((err.line(), err.span()), (None, 4..7));
+    }
+}
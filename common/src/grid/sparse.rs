@@ -0,0 +1,258 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Add;
+use super::{wrap_into, Grid, Neighbourhood, Point, Point3, Rect};
+
+/// A coordinate a [SparseGrid] can be keyed by.
+pub trait Coordinate: Copy + Eq + Hash + Add<Output=Self> {
+    /// The smallest value of every component.
+    fn component_min(self, other: Self) -> Self;
+
+    /// The largest value of every component.
+    fn component_max(self, other: Self) -> Self;
+
+    /// Whether any component lies on the edge of the box between `min` and `max`.
+    fn on_edge(self, min: Self, max: Self) -> bool;
+}
+
+impl Coordinate for Point {
+    fn component_min(self, other: Self) -> Self {
+        Point::new(self.x.min(other.x), self.y.min(other.y))
+    }
+
+    fn component_max(self, other: Self) -> Self {
+        Point::new(self.x.max(other.x), self.y.max(other.y))
+    }
+
+    fn on_edge(self, min: Self, max: Self) -> bool {
+        self.x == min.x || self.y == min.y || self.x == max.x || self.y == max.y
+    }
+}
+
+impl Coordinate for Point3 {
+    fn component_min(self, other: Self) -> Self {
+        Point3::new(self.x.min(other.x), self.y.min(other.y), self.z.min(other.z))
+    }
+
+    fn component_max(self, other: Self) -> Self {
+        Point3::new(self.x.max(other.x), self.y.max(other.y), self.z.max(other.z))
+    }
+
+    fn on_edge(self, min: Self, max: Self) -> bool {
+        self.x == min.x || self.y == min.y || self.z == min.z
+            || self.x == max.x || self.y == max.y || self.z == max.z
+    }
+}
+
+/// A grid without fixed size that only stores the cells that were set, for maps that are mostly
+/// empty or grow without limit.
+///
+/// Positions may be negative. The bounding box of all set cells is kept up to date as cells are
+/// inserted and removed.
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T, P = Point> {
+    cells: HashMap<P, T>,
+    /// The smallest and largest corner of all cells.
+    bounds: Option<(P, P)>,
+}
+
+impl<T, P: Coordinate> SparseGrid<T, P> {
+
+    pub fn new() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The smallest and largest corner of the box containing all cells.
+    pub fn bounds(&self) -> Option<(P, P)> {
+        self.bounds
+    }
+
+    pub fn contains(&self, p: P) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    pub fn get(&self, p: P) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    pub fn get_mut(&mut self, p: P) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    /// Set a cell, returning the previous value.
+    pub fn insert(&mut self, p: P, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            None => (p, p),
+            Some((min, max)) => (min.component_min(p), max.component_max(p)),
+        });
+        self.cells.insert(p, value)
+    }
+
+    /// Clear a cell, returning its value.
+    ///
+    /// Removing a cell on the edge of the bounds recomputes them from all remaining cells.
+    pub fn remove(&mut self, p: P) -> Option<T> {
+        let value = self.cells.remove(&p)?;
+        if self.bounds.is_some_and(|(min, max)| p.on_edge(min, max)) {
+            self.bounds = self.cells.keys().fold(None, |bounds, &p| Some(match bounds {
+                None => (p, p),
+                Some((min, max)) => (min.component_min(p), max.component_max(p)),
+            }));
+        }
+        Some(value)
+    }
+
+    /// All set cells, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item=(P, &T)> {
+        self.cells.iter().map(|(p, value)| (*p, value))
+    }
+
+    /// The set neighbours of a position at arbitrary offsets, e.g. [Point3::ORTHOGONAL].
+    pub fn neighbours_at<'a>(&'a self, p: P, offsets: &'a [P]) -> impl Iterator<Item=(P, &'a T)> + 'a {
+        offsets.iter().filter_map(move |offset| {
+            let n = p + *offset;
+            self.get(n).map(|value| (n, value))
+        })
+    }
+}
+
+impl<T> SparseGrid<T, Point> {
+
+    /// The rectangle containing all cells.
+    pub fn rect(&self) -> Option<Rect> {
+        self.bounds.map(|(min, max)| Rect::new(min, max))
+    }
+
+    /// The set neighbours of a position, like [Grid::neighbours]. A wrapping neighbourhood wraps
+    /// around the current bounds.
+    pub fn neighbours<'a>(&'a self, p: Point, neighbourhood: &'a Neighbourhood) -> impl Iterator<Item=(Point, &'a T)> + 'a {
+        neighbourhood.around(p).filter_map(move |n| {
+            let n = if neighbourhood.wraps() { self.wrap(n)? } else { n };
+            self.get(n).map(|value| (n, value))
+        })
+    }
+
+    fn wrap(&self, p: Point) -> Option<Point> {
+        let rect = self.rect()?;
+        wrap_into(rect.width() as usize, rect.height() as usize, p - rect.min).map(|offset| rect.min + offset)
+    }
+
+    /// The cells of a dense grid that `keep` accepts.
+    pub fn from_grid(grid: &Grid<T>, mut keep: impl FnMut(Point, &T) -> bool) -> Self where T: Clone {
+        grid.iter()
+            .filter(|(p, value)| keep(*p, value))
+            .map(|(p, value)| (p, value.clone()))
+            .collect()
+    }
+
+    /// A dense grid covering [SparseGrid::rect], with `empty` in all cells that are not set. The top
+    /// left corner of the bounds becomes `(0, 0)`.
+    pub fn to_grid(&self, empty: T) -> Grid<T> where T: Clone {
+        let Some(rect) = self.rect() else {
+            return Grid::new(0, 0, empty);
+        };
+        Grid::from_fn(rect.width() as usize, rect.height() as usize, |p| {
+            self.get(rect.min + p).unwrap_or(&empty).clone()
+        })
+    }
+}
+
+impl<T> From<Grid<T>> for SparseGrid<T, Point> {
+    fn from(grid: Grid<T>) -> Self {
+        let width = grid.width();
+        grid.cells.into_iter()
+            .enumerate()
+            .map(|(idx, value)| (Point::new((idx % width) as i64, (idx / width) as i64), value))
+            .collect()
+    }
+}
+
+impl<T: PartialEq, P: Coordinate> PartialEq for SparseGrid<T, P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cells == other.cells
+    }
+}
+
+impl<T: Eq, P: Coordinate> Eq for SparseGrid<T, P> {}
+
+impl<T, P: Coordinate> Default for SparseGrid<T, P> {
+    fn default() -> Self {
+        SparseGrid::new()
+    }
+}
+
+impl<T, P: Coordinate> FromIterator<(P, T)> for SparseGrid<T, P> {
+    fn from_iter<I: IntoIterator<Item=(P, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        for (p, value) in iter {
+            grid.insert(p, value);
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tracks_bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.rect(), None);
+        grid.insert(Point::new(2, -3), 'a');
+        grid.insert(Point::new(-1, 4), 'b');
+        grid.insert(Point::new(0, 0), 'c');
+        assert_eq!(grid.rect(), Some(Rect::new(Point::new(-1, -3), Point::new(2, 4))));
+        assert_eq!(grid.remove(Point::new(0, 0)), Some('c'));
+        assert_eq!(grid.rect(), Some(Rect::new(Point::new(-1, -3), Point::new(2, 4))));
+        assert_eq!(grid.remove(Point::new(-1, 4)), Some('b'));
+        assert_eq!(grid.rect(), Some(Rect::point(Point::new(2, -3))));
+        assert_eq!(grid.remove(Point::new(-1, 4)), None);
+        grid.remove(Point::new(2, -3));
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounds(), None);
+    }
+
+    #[test]
+    fn neighbours_match_dense_grid() {
+        let dense: Grid<char> = "#.#\n.##\n#..".parse().unwrap();
+        let sparse = SparseGrid::from_grid(&dense, |_, c| *c == '#');
+        for hood in [Neighbourhood::ALL, Neighbourhood::ORTHOGONAL.wrapping()] {
+            for p in dense.points() {
+                let expected: Vec<_> = dense.neighbours(p, &hood).filter(|(_, c)| **c == '#').collect();
+                assert_eq!(sparse.neighbours(p, &hood).collect::<Vec<_>>(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn converts_to_dense() {
+        let dense: Grid<char> = "ab\ncd".parse().unwrap();
+        let mut sparse = SparseGrid::from(dense.clone());
+        assert_eq!(sparse.len(), 4);
+        assert_eq!(sparse.to_grid('.'), dense);
+        sparse.insert(Point::new(-1, 2), 'e');
+        assert_eq!(sparse.to_grid('.').to_string(), ".ab\n.cd\ne..\n");
+    }
+
+    #[test]
+    fn three_dimensions() {
+        let cubes: SparseGrid<(), Point3> = [Point3::new(1, 1, 1), Point3::new(2, 1, 1), Point3::new(1, 1, 3)]
+            .into_iter()
+            .map(|p| (p, ()))
+            .collect();
+        assert_eq!(cubes.bounds(), Some((Point3::new(1, 1, 1), Point3::new(2, 1, 3))));
+        assert_eq!(cubes.neighbours_at(Point3::new(1, 1, 2), &Point3::ORTHOGONAL).count(), 2);
+    }
+}
@@ -0,0 +1,266 @@
+use std::fmt::{Display, Formatter};
+use std::ops::Index;
+use super::{Grid, Point, Rect};
+
+/// A read only, transformed view of a [Grid] that does not copy any cells.
+///
+/// Rotating, flipping, transposing and cropping only change how view positions map to grid positions, so
+/// any number of them costs the same on access as none. Every tiling adds one modulo operation per access.
+#[derive(Debug, Clone)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    width: usize,
+    height: usize,
+    /// The grid position of the top left cell of the view.
+    origin: Point,
+    /// The steps in the grid when moving one cell right or down in the view.
+    dx: Point,
+    dy: Point,
+    /// The areas that tiling wraps positions into, the most recent one first.
+    tiles: Vec<Rect>,
+}
+
+impl<T> Grid<T> {
+
+    /// A view of the whole grid, as the starting point for transforms.
+    pub fn view(&self) -> GridView<'_, T> {
+        GridView {
+            grid: self,
+            width: self.width,
+            height: self.height,
+            origin: Point::new(0, 0),
+            dx: Point::RIGHT,
+            dy: Point::DOWN,
+            tiles: Vec::new(),
+        }
+    }
+}
+
+impl<'a, T> GridView<'a, T> {
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.x >= 0 && p.y >= 0 && (p.x as usize) < self.width && (p.y as usize) < self.height
+    }
+
+    /// The position in the underlying grid a position of the view shows.
+    pub fn source(&self, p: Point) -> Option<Point> {
+        if !self.contains(p) {
+            return None;
+        }
+        let mut source = self.origin + self.dx * p.x + self.dy * p.y;
+        for tile in &self.tiles {
+            let size = Point::new(tile.width() as i64, tile.height() as i64);
+            let offset = source - tile.min;
+            source = tile.min + Point::new(offset.x.rem_euclid(size.x), offset.y.rem_euclid(size.y));
+        }
+        Some(source)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&'a T> {
+        self.grid.get(self.source(p)?)
+    }
+
+    /// The value at a position of the view, panicking if it is outside.
+    fn cell(&self, p: Point) -> &'a T {
+        self.get(p).unwrap_or_else(|| panic!("{} is outside of the {}x{} view", p, self.width, self.height))
+    }
+
+    /// All positions of the view in reading order.
+    pub fn points(&self) -> impl Iterator<Item=Point> {
+        let width = self.width;
+        (0..self.width * self.height).map(move |idx| Point::new((idx % width) as i64, (idx / width) as i64))
+    }
+
+    /// All positions of the view with their values, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item=(Point, &'a T)> + '_ {
+        self.points().map(|p| (p, self.cell(p)))
+    }
+
+    /// The values of one row of the view.
+    pub fn row(&self, y: usize) -> impl Iterator<Item=&'a T> + '_ {
+        let len = if y < self.height { self.width } else { 0 };
+        (0..len).map(move |x| self.cell(Point::new(x as i64, y as i64)))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item=impl Iterator<Item=&'a T> + '_> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// Copy the cells of the view into a new grid.
+    pub fn to_grid(&self) -> Grid<T> where T: Clone {
+        Grid::from_fn(self.width, self.height, |p| self[p].clone())
+    }
+
+    /// Rotate by 90 degrees clockwise.
+    pub fn rotate_cw(self) -> Self {
+        let origin = self.origin + self.dy * (self.height as i64 - 1);
+        GridView {
+            width: self.height,
+            height: self.width,
+            origin,
+            dx: -self.dy,
+            dy: self.dx,
+            ..self
+        }
+    }
+
+    /// Rotate by 90 degrees counterclockwise.
+    pub fn rotate_ccw(self) -> Self {
+        let origin = self.origin + self.dx * (self.width as i64 - 1);
+        GridView {
+            width: self.height,
+            height: self.width,
+            origin,
+            dx: self.dy,
+            dy: -self.dx,
+            ..self
+        }
+    }
+
+    /// Rotate by 180 degrees.
+    pub fn rotate_180(self) -> Self {
+        self.flip_horizontal().flip_vertical()
+    }
+
+    /// Mirror along the main diagonal, so that rows become columns.
+    pub fn transpose(self) -> Self {
+        GridView {
+            width: self.height,
+            height: self.width,
+            dx: self.dy,
+            dy: self.dx,
+            ..self
+        }
+    }
+
+    /// Mirror left to right.
+    pub fn flip_horizontal(self) -> Self {
+        GridView {
+            origin: self.origin + self.dx * (self.width as i64 - 1),
+            dx: -self.dx,
+            ..self
+        }
+    }
+
+    /// Mirror top to bottom.
+    pub fn flip_vertical(self) -> Self {
+        GridView {
+            origin: self.origin + self.dy * (self.height as i64 - 1),
+            dy: -self.dy,
+            ..self
+        }
+    }
+
+    /// The part of the view inside `area`. Whatever of `area` lies outside of the view is cut off.
+    pub fn crop(self, area: Rect) -> Self {
+        let min = Point::new(area.min.x.max(0), area.min.y.max(0));
+        let max = Point::new(area.max.x.min(self.width as i64 - 1), area.max.y.min(self.height as i64 - 1));
+        if min.x > max.x || min.y > max.y {
+            return GridView { width: 0, height: 0, ..self };
+        }
+        GridView {
+            width: (max.x - min.x + 1) as usize,
+            height: (max.y - min.y + 1) as usize,
+            origin: self.origin + self.dx * min.x + self.dy * min.y,
+            ..self
+        }
+    }
+
+    /// Repeat the view `across` times to the right and `down` times downwards.
+    pub fn tile(mut self, across: usize, down: usize) -> Self {
+        if self.is_empty() {
+            return self;
+        }
+        // The grid area the view currently shows. Positions beyond it wrap back into it.
+        let far_corner = self.origin + self.dx * (self.width as i64 - 1) + self.dy * (self.height as i64 - 1);
+        self.tiles.insert(0, Rect::new(self.origin, far_corner));
+        self.width *= across;
+        self.height *= down;
+        self
+    }
+}
+
+impl<T> Index<Point> for GridView<'_, T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &Self::Output {
+        self.cell(p)
+    }
+}
+
+impl<T: Display> Display for GridView<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        "abc\ndef".parse().unwrap()
+    }
+
+    #[test]
+    fn rotations() {
+        let grid = grid();
+        assert_eq!(grid.view().rotate_cw().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.view().rotate_ccw().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.view().rotate_180().to_string(), "fed\ncba\n");
+        assert_eq!(grid.view().rotate_cw().rotate_cw().to_string(), "fed\ncba\n");
+        let full_turn = (0..4).fold(grid.view(), |view, _| view.rotate_cw());
+        assert_eq!(full_turn.to_grid(), grid);
+        assert_eq!(grid.view().rotate_cw().rotate_ccw().to_grid(), grid);
+    }
+
+    #[test]
+    fn mirrors() {
+        let grid = grid();
+        assert_eq!(grid.view().transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.view().flip_horizontal().to_string(), "cba\nfed\n");
+        assert_eq!(grid.view().flip_vertical().to_string(), "def\nabc\n");
+        assert_eq!(grid.view().rotate_cw().flip_horizontal().to_grid(), grid.view().transpose().to_grid());
+    }
+
+    #[test]
+    fn crop() {
+        let grid = grid();
+        let view = grid.view().crop(Rect::new(Point::new(1, 0), Point::new(5, 1)));
+        assert_eq!(view.to_string(), "bc\nef\n");
+        assert_eq!(view.source(Point::new(0, 1)), Some(Point::new(1, 1)));
+        assert_eq!(view.get(Point::new(2, 0)), None);
+        let rotated = grid.view().rotate_cw().crop(Rect::point(Point::new(1, 2)));
+        assert_eq!(rotated.to_string(), "c\n");
+        assert!(grid.view().crop(Rect::point(Point::new(3, 0))).is_empty());
+    }
+
+    #[test]
+    fn tile() {
+        let grid = grid();
+        assert_eq!(grid.view().tile(2, 2).to_string(), "abcabc\ndefdef\nabcabc\ndefdef\n");
+        let column = grid.view().crop(Rect::new(Point::new(1, 0), Point::new(1, 1)));
+        assert_eq!(column.clone().tile(3, 1).to_string(), "bbb\neee\n");
+        assert_eq!(column.tile(1, 2).rotate_cw().to_string(), "ebeb\n");
+        let nested = grid.view().rotate_cw().tile(1, 2).crop(Rect::new(Point::new(0, 2), Point::new(1, 3))).tile(2, 1);
+        assert_eq!(nested.to_string(), "fcfc\ndada\n");
+    }
+}
@@ -0,0 +1,252 @@
+use std::io::BufRead;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use crate::{ParseError, SolutionError};
+
+/// Puzzle input split into lines, the same way for every day.
+///
+/// Lines may end with `\n` or `\r\n`. Indentation that all lines have in common is removed, so that
+/// indented raw strings in tests parse like the real input. Lines that contain only whitespace are
+/// blank: they separate sections and are skipped otherwise. Line numbers are 1-based and count blank
+/// lines too, so they match the original text.
+#[derive(Debug, Clone)]
+pub struct Input<'a> {
+    /// All lines, blank ones included.
+    lines: Vec<Line<'a>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Line<'a> {
+    number: usize,
+    /// The line as it appears in the input, for error messages.
+    raw: &'a str,
+    /// The line without the common indentation.
+    text: &'a str,
+}
+
+impl<'a> Input<'a> {
+
+    pub fn new(text: &'a str) -> Self {
+        let indentation = text.lines()
+            .filter(|line| !is_blank(line))
+            .map(|line| &line[..line.len() - line.trim_start().len()])
+            .reduce(|common, indent| {
+                let len = common.char_indices()
+                    .zip(indent.chars())
+                    .find(|((_, a), b)| a != b)
+                    .map_or(common.len().min(indent.len()), |((idx, _), _)| idx);
+                &common[..len]
+            })
+            .unwrap_or("");
+        let lines = text.lines()
+            .enumerate()
+            .map(|(idx, line)| Line {
+                number: idx + 1,
+                raw: line,
+                text: line.strip_prefix(indentation).unwrap_or(line),
+            })
+            .collect();
+        Input { lines }
+    }
+
+    /// All non-blank lines with their line numbers.
+    pub fn lines(&self) -> impl Iterator<Item=(usize, &'a str)> + '_ {
+        self.lines.iter()
+            .filter(|line| !is_blank(line.text))
+            .map(|line| (line.number, line.text))
+    }
+
+    /// The groups of lines that are separated by one or more blank lines.
+    pub fn sections(&self) -> impl Iterator<Item=Section<'a, '_>> {
+        self.lines.split(|line| is_blank(line.text))
+            .filter(|lines| !lines.is_empty())
+            .map(|lines| Section { lines })
+    }
+
+    /// Parse every non-blank line, attaching the line to the error of the first one that fails.
+    pub fn parse_lines<T: FromStr<Err=ParseError>>(&self) -> Result<Vec<T>, ParseError> {
+        parse_lines(self.lines.iter().filter(|line| !is_blank(line.text)))
+    }
+
+    /// Attach line `number` to an error that is relative to the line without the common indentation,
+    /// e.g. one that came from parsing the lines joined together. Replaces a line that is already attached.
+    pub fn in_line(&self, error: ParseError, number: usize) -> ParseError {
+        match self.lines.get(number.wrapping_sub(1)) {
+            Some(line) => error.in_line(line.number, line.raw, line.text),
+            None => error,
+        }
+    }
+
+    /// The whole input without the common indentation, for parsers that take the text as it is, such
+    /// as the one of [Grid](crate::grid::Grid). Blank lines are kept, but emptied, so that line numbers
+    /// do not change.
+    pub fn text(&self) -> String {
+        self.lines.iter()
+            .map(|line| if is_blank(line.text) { "" } else { line.text })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Consecutive non-blank lines of an [Input].
+#[derive(Debug, Clone, Copy)]
+pub struct Section<'a, 'b> {
+    lines: &'b [Line<'a>],
+}
+
+impl<'a> Section<'a, '_> {
+
+    /// The lines of the section with their line numbers.
+    pub fn lines(&self) -> impl Iterator<Item=(usize, &'a str)> + '_ {
+        self.lines.iter().map(|line| (line.number, line.text))
+    }
+
+    /// The line number of the first line.
+    pub fn first_line(&self) -> usize {
+        self.lines[0].number
+    }
+
+    /// The lines of the section joined with `\n`, for parsers that need the section as one string.
+    pub fn text(&self) -> String {
+        self.lines.iter().map(|line| line.text).collect::<Vec<_>>().join("\n")
+    }
+
+    /// Parse every line, attaching the line to the error of the first one that fails.
+    pub fn parse_lines<T: FromStr<Err=ParseError>>(&self) -> Result<Vec<T>, ParseError> {
+        parse_lines(self.lines.iter())
+    }
+}
+
+fn parse_lines<'a, 'b, T>(lines: impl Iterator<Item=&'b Line<'a>>) -> Result<Vec<T>, ParseError>
+    where T: FromStr<Err=ParseError>,
+          'a: 'b
+{
+    lines.map(|line| T::from_str(line.text).map_err(|e| e.in_line(line.number, line.raw, line.text)))
+        .collect()
+}
+
+/// Parse the lines of a reader one at a time, without reading all of the input first.
+///
+/// Yields every record with its 1-based line number. Like [Input::lines], blank lines are skipped,
+/// but since the input is not known in advance, indentation is not removed. Iteration stops after
+/// the first error in reading.
+pub fn records<R: BufRead, T: FromStr<Err=ParseError>>(reader: R) -> Records<R, T> {
+    Records {
+        reader,
+        buf: String::new(),
+        number: 0,
+        failed: false,
+        record: PhantomData,
+    }
+}
+
+/// Iterator returned by [records].
+pub struct Records<R, T> {
+    reader: R,
+    /// The current line, reused for every line.
+    buf: String,
+    number: usize,
+    failed: bool,
+    record: PhantomData<T>,
+}
+
+impl<R: BufRead, T: FromStr<Err=ParseError>> Iterator for Records<R, T> {
+    type Item = Result<(usize, T), SolutionError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.failed {
+            self.buf.clear();
+            match self.reader.read_line(&mut self.buf) {
+                Ok(0) => return None,
+                Ok(_) => self.number += 1,
+                Err(e) => {
+                    self.failed = true;
+                    return Some(Err(SolutionError::Read(format!("line {}: {}", self.number + 1, e))));
+                }
+            }
+            let line = self.buf.trim_end_matches(['\n', '\r']);
+            if is_blank(line) {
+                continue;
+            }
+            return Some(T::from_str(line)
+                .map(|record| (self.number, record))
+                .map_err(|e| e.in_line(self.number, line, line).into()));
+        }
+        None
+    }
+}
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn dedents_and_skips_blank_lines() {
+        let input = Input::new("\n    a b\n      c\r\n  \n    d\n  ");
+        assert_eq!(input.lines().collect::<Vec<_>>(), [(2, "a b"), (3, "  c"), (5, "d")]);
+        assert_eq!(Input::new("\ta\n  b\n").lines().collect::<Vec<_>>(), [(1, "\ta"), (2, "  b")]);
+        assert_eq!(Input::new("").lines().count(), 0);
+        assert_eq!(input.text(), "\na b\n  c\n\nd\n");
+    }
+
+    #[test]
+    fn sections() {
+        let input = Input::new("a\nb\n\n\n  \nc\r\n\r\nd\ne\n");
+        let sections: Vec<_> = input.sections().map(|s| (s.first_line(), s.text())).collect();
+        assert_eq!(sections, [(1, "a\nb".to_string()), (6, "c".to_string()), (8, "d\ne".to_string())]);
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Number(u32);
+
+    impl FromStr for Number {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            s.trim().parse::<u32>().map(Number).map_err(|_| ParseError::at(s, s, "Not a number"))
+        }
+    }
+
+    #[test]
+    fn parse_errors_know_their_line() {
+        let err = Input::new("  1\n\n  x2\n").parse_lines::<Number>().err().unwrap();
+        assert_eq!(err.line(), Some(3));
+        assert_eq!(err.span(), 2..4);
+        assert!(Input::new("1\n2").parse_lines::<Number>().is_ok());
+    }
+
+    #[test]
+    fn attach_line_to_dedented_error() {
+        let input = Input::new("\n    ab\n    cd\n");
+        let err = input.in_line(ParseError::new("Bad cell", 1..2).in_line(1, "cd", "cd"), 3);
+        assert_eq!((err.line(), err.column(), err.span()), (Some(3), Some(6), 5..6));
+    }
+
+    #[test]
+    fn streams_records() {
+        let input = "1\r\n\n  2\n3";
+        let numbers: Vec<_> = records::<_, Number>(input.as_bytes()).map(Result::unwrap).collect();
+        assert_eq!(numbers, [(1, Number(1)), (3, Number(2)), (4, Number(3))]);
+
+        let mut stream = records::<_, Number>("4\nx\n5\n".as_bytes());
+        assert_eq!(stream.next(), Some(Ok((1, Number(4)))));
+        let Some(Err(SolutionError::Parse(err))) = stream.next() else {
+            panic!("Expected a parse error");
+        };
+        assert_eq!(err.line(), Some(2));
+        assert_eq!(stream.next(), Some(Ok((3, Number(5)))));
+        assert_eq!(stream.next(), None);
+    }
+
+    #[test]
+    fn stops_after_read_error() {
+        let mut stream = records::<_, Number>(&[b'1', b'\n', 0xff, b'\n'][..]);
+        assert_eq!(stream.next(), Some(Ok((1, Number(1)))));
+        assert!(matches!(stream.next(), Some(Err(SolutionError::Read(_)))));
+        assert_eq!(stream.next(), None);
+    }
+}
@@ -0,0 +1,177 @@
+// Lets code generated by `common_derive`, which refers to `::common`, be used in this crate too.
+extern crate self as common;
+
+mod error;
+pub mod grid;
+mod input;
+pub mod parse;
+pub mod search;
+
+use std::any::Any;
+use std::fmt::{Display, Formatter};
+use std::io::BufRead;
+use anyhow::anyhow;
+
+pub use error::{checked_sum, CheckedAdd, ParseError, SolutionError};
+pub use input::{records, Input, Records, Section};
+
+/// One of the two parts every puzzle consists of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => f.pad("1"),
+            Part::Two => f.pad("2"),
+        }
+    }
+}
+
+impl From<Part> for u8 {
+    fn from(part: Part) -> Self {
+        match part {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+/// The solution to a single day of the Advent of Code.
+///
+/// The puzzle input is parsed once with [Solution::parse] and the result is handed to both parts.
+/// Parts that are not solved yet keep the default implementation, which returns
+/// [SolutionError::Unsolved]. Invalid input is reported as an error, never with a panic.
+pub trait Solution {
+    /// The day in December this puzzle belongs to.
+    const DAY: u8;
+
+    /// The puzzle input after parsing.
+    type Input;
+
+    /// The type of the answer both parts produce.
+    type Answer: Display;
+
+    /// Parse the raw puzzle input.
+    fn parse(&self, input: &str) -> Result<Self::Input, SolutionError>;
+
+    fn part_one(&self, _input: &Self::Input) -> Result<Self::Answer, SolutionError> {
+        Err(SolutionError::Unsolved { day: Self::DAY, part: Part::One })
+    }
+
+    fn part_two(&self, _input: &Self::Input) -> Result<Self::Answer, SolutionError> {
+        Err(SolutionError::Unsolved { day: Self::DAY, part: Part::Two })
+    }
+
+    /// Solve one part on already parsed input.
+    fn solve_parsed(&self, part: Part, input: &Self::Input) -> Result<Self::Answer, SolutionError> {
+        match part {
+            Part::One => self.part_one(input),
+            Part::Two => self.part_two(input),
+        }
+    }
+
+    /// Parse the raw input and solve one part on it.
+    fn solve(&self, part: Part, input: &str) -> Result<Self::Answer, SolutionError> {
+        let parsed = self.parse(input)?;
+        self.solve_parsed(part, &parsed)
+    }
+
+    /// Solve one part while reading the input line by line, so that inputs larger than the memory
+    /// can be solved. Returns `None` for days that need the whole input at once, which is the default.
+    /// Those days must not read from the input, so that it can still be read whole instead.
+    fn solve_stream(&self, _part: Part, _input: &mut dyn BufRead) -> Option<Result<Self::Answer, SolutionError>> {
+        None
+    }
+}
+
+/// Object safe view of a [Solution], so that days with different input and answer types can be
+/// stored side by side in a registry.
+///
+/// The parsed input is passed around type erased. It is parsed once and can then be used for both
+/// parts.
+pub trait DynSolution: Sync {
+    fn day(&self) -> u8;
+
+    /// The name of the answer type, e.g. `u32`.
+    fn answer_type(&self) -> &'static str;
+
+    /// Parse the raw input.
+    fn parse_dyn(&self, input: &str) -> anyhow::Result<Box<dyn Any>>;
+
+    /// Solve one part on input returned by [DynSolution::parse_dyn] and format the answer.
+    fn solve_dyn(&self, part: Part, input: &dyn Any) -> anyhow::Result<String>;
+
+    /// Solve one part with [Solution::solve_stream] and format the answer. `None` if the day does not
+    /// support streaming.
+    fn stream_dyn(&self, part: Part, input: &mut dyn BufRead) -> Option<anyhow::Result<String>>;
+}
+
+impl<S> DynSolution for S
+    where S: Solution + Sync,
+          S::Input: 'static
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn answer_type(&self) -> &'static str {
+        std::any::type_name::<S::Answer>()
+    }
+
+    fn parse_dyn(&self, input: &str) -> anyhow::Result<Box<dyn Any>> {
+        Ok(Box::new(self.parse(input)?))
+    }
+
+    fn solve_dyn(&self, part: Part, input: &dyn Any) -> anyhow::Result<String> {
+        let input = input.downcast_ref::<S::Input>()
+            .ok_or(anyhow!("Input was not parsed by day {}.", S::DAY))?;
+        Ok(self.solve_parsed(part, input)?.to_string())
+    }
+
+    fn stream_dyn(&self, part: Part, input: &mut dyn BufRead) -> Option<anyhow::Result<String>> {
+        let answer = self.solve_stream(part, input)?;
+        Some(answer.map(|a| a.to_string()).map_err(anyhow::Error::from))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Lines;
+
+    impl Solution for Lines {
+        const DAY: u8 = 0;
+        type Input = Vec<u32>;
+        type Answer = u32;
+
+        fn parse(&self, input: &str) -> Result<Self::Input, SolutionError> {
+            Ok(input.lines().map(|l| l.len() as u32).collect())
+        }
+
+        fn part_one(&self, input: &Self::Input) -> Result<Self::Answer, SolutionError> {
+            Ok(input.iter().sum())
+        }
+    }
+
+    #[test]
+    fn dispatches_parts() {
+        assert_eq!(Lines.solve(Part::One, "ab\ncde").unwrap(), 5);
+        assert_eq!(Lines.solve(Part::Two, "ab\ncde"), Err(SolutionError::Unsolved { day: 0, part: Part::Two }));
+    }
+
+    #[test]
+    fn dyn_solution_formats_answer() {
+        let solution: &dyn DynSolution = &Lines;
+        assert_eq!(solution.day(), 0);
+        assert_eq!(solution.answer_type(), "u32");
+        let input = solution.parse_dyn("abc").unwrap();
+        assert_eq!(solution.solve_dyn(Part::One, input.as_ref()).unwrap(), "3");
+        assert!(solution.solve_dyn(Part::One, &5u8).is_err());
+        assert!(solution.stream_dyn(Part::One, &mut "abc".as_bytes()).is_none());
+    }
+}
@@ -0,0 +1,408 @@
+//! Small parser combinators for line based puzzle formats.
+//!
+//! A parser takes the remaining input and returns the rest after what it consumed, together with
+//! the parsed value. Parsers only ever return subslices of their input, so a [Failure] knows exactly
+//! which token it is about and [parse_all] turns it into a [ParseError] pointing at that token.
+//!
+//! ```
+//! # use common::parse::{keyword, literal, parse_all, separated, uint, ws, Parser};
+//! let pull = (ws(uint::<u32>()), ws(keyword([("red", 'r'), ("blue", 'b')])));
+//! let game = (ws(literal("Game")), ws(uint::<u32>()), ws(literal(":")), separated(pull, ws(literal(","))))
+//!     .map(|(_, id, _, pulls)| (id, pulls));
+//! assert_eq!(parse_all(&game, "Game 7: 3 blue, 4 red").unwrap(), (7, vec![(3, 'b'), (4, 'r')]));
+//! assert_eq!(parse_all(&game, "Game 7: 3 blue, 4 pink").unwrap_err().span(), 18..22);
+//! ```
+
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::num::ParseIntError;
+use std::ops::Range;
+use std::str::FromStr;
+use crate::ParseError;
+
+pub use common_derive::LineParse;
+
+/// Why a parser did not accept its input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure<'a> {
+    /// The offending token, a subslice of the input. Empty at the end of the input.
+    pub token: &'a str,
+    pub message: String,
+}
+
+/// The rest of the input and the parsed value, or why parsing failed.
+pub type PResult<'a, O> = Result<(&'a str, O), Failure<'a>>;
+
+pub trait Parser<'a, O> {
+    fn parse(&self, input: &'a str) -> PResult<'a, O>;
+
+    /// Transform the parsed value.
+    fn map<U>(self, f: impl Fn(O) -> U) -> impl Parser<'a, U>
+        where Self: Sized
+    {
+        move |input| self.parse(input).map(|(rest, value)| (rest, f(value)))
+    }
+}
+
+impl<'a, O, F> Parser<'a, O> for F
+    where F: Fn(&'a str) -> PResult<'a, O>
+{
+    fn parse(&self, input: &'a str) -> PResult<'a, O> {
+        self(input)
+    }
+}
+
+/// Tuples of parsers run one after another and return all values.
+macro_rules! sequence {
+    ($($p:ident $o:ident),+) => {
+        impl<'a, $($o,)+ $($p: Parser<'a, $o>,)+> Parser<'a, ($($o,)+)> for ($($p,)+) {
+            #[allow(non_snake_case)]
+            fn parse(&self, input: &'a str) -> PResult<'a, ($($o,)+)> {
+                let ($($p,)+) = self;
+                $(let (input, $o) = $p.parse(input)?;)+
+                Ok((input, ($($o,)+)))
+            }
+        }
+    };
+}
+
+sequence!(A OA, B OB);
+sequence!(A OA, B OB, C OC);
+sequence!(A OA, B OB, C OC, D OD);
+sequence!(A OA, B OB, C OC, D OD, E OE);
+sequence!(A OA, B OB, C OC, D OD, E OE, F OF);
+
+/// The token a failure at the start of `input` is about: the word there, or a single character
+/// that is not part of a word.
+fn token(input: &str) -> &str {
+    let word = input.find(|c: char| !c.is_alphanumeric()).unwrap_or(input.len());
+    match word {
+        0 => &input[..input.chars().next().map_or(0, char::len_utf8)],
+        len => &input[..len],
+    }
+}
+
+fn fail<T>(input: &str, message: impl Into<String>) -> Result<T, Failure<'_>> {
+    Err(Failure {
+        token: token(input),
+        message: message.into(),
+    })
+}
+
+/// Exactly the text `expected`.
+pub fn literal<'a>(expected: &'static str) -> impl Parser<'a, ()> {
+    move |input: &'a str| match input.strip_prefix(expected) {
+        Some(rest) => Ok((rest, ())),
+        None => fail(input, format!("Expected '{}'", expected)),
+    }
+}
+
+/// A whole word that is one of the keywords, returning the value that belongs to it.
+pub fn keyword<'a, T: Clone, const N: usize>(keywords: [(&'static str, T); N]) -> impl Parser<'a, T> {
+    move |input: &'a str| {
+        let word = token(input);
+        match keywords.iter().find(|(keyword, _)| *keyword == word) {
+            Some((_, value)) => Ok((&input[word.len()..], value.clone())),
+            None => {
+                let expected: Vec<_> = keywords.iter().map(|(keyword, _)| *keyword).collect();
+                fail(input, format!("Expected one of {}", expected.join(", ")))
+            }
+        }
+    }
+}
+
+fn number<'a, T>(input: &'a str, len: usize) -> PResult<'a, T>
+    where T: FromStr,
+          T::Err: Display
+{
+    let (digits, rest) = input.split_at(len);
+    if rest.starts_with(char::is_alphanumeric) {
+        return fail(input, format!("Invalid number '{}'", token(input)));
+    }
+    match T::from_str(digits) {
+        Ok(n) => Ok((rest, n)),
+        Err(_) if digits.is_empty() => fail(input, "Expected a number"),
+        Err(e) => Err(Failure { token: digits, message: format!("Invalid number: {}", e) }),
+    }
+}
+
+/// An unsigned integer, as a run of ASCII digits.
+pub fn uint<'a, T>() -> impl Parser<'a, T>
+    where T: FromStr,
+          T::Err: Display
+{
+    |input: &'a str| number(input, input.find(|c: char| !c.is_ascii_digit()).unwrap_or(input.len()))
+}
+
+/// An integer with an optional `-` or `+` sign.
+pub fn int<'a, T>() -> impl Parser<'a, T>
+    where T: FromStr,
+          T::Err: Display
+{
+    |input: &'a str| {
+        let sign = usize::from(input.starts_with(['-', '+']));
+        let digits = input[sign..].find(|c: char| !c.is_ascii_digit()).unwrap_or(input.len() - sign);
+        if digits == 0 {
+            return fail(input, "Expected a number");
+        }
+        number(input, sign + digits)
+    }
+}
+
+/// Skip spaces and tabs before `parser`.
+pub fn ws<'a, O>(parser: impl Parser<'a, O>) -> impl Parser<'a, O> {
+    move |input: &'a str| parser.parse(input.trim_start_matches([' ', '\t']))
+}
+
+/// One or more values separated by `separator`.
+pub fn separated<'a, O, S>(item: impl Parser<'a, O>, separator: impl Parser<'a, S>) -> impl Parser<'a, Vec<O>> {
+    move |input: &'a str| {
+        let (mut input, first) = item.parse(input)?;
+        let mut items = vec![first];
+        while let Ok((rest, _)) = separator.parse(input) {
+            let (rest, next) = item.parse(rest)?;
+            items.push(next);
+            input = rest;
+        }
+        Ok((input, items))
+    }
+}
+
+/// As many values as `item` accepts, possibly none.
+pub fn many<'a, O>(item: impl Parser<'a, O>) -> impl Parser<'a, Vec<O>> {
+    move |mut input: &'a str| {
+        let mut items = Vec::new();
+        while let Ok((rest, next)) = item.parse(input) {
+            items.push(next);
+            input = rest;
+        }
+        Ok((input, items))
+    }
+}
+
+/// Where a field of a line pattern ends, see [field] and [list].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Until {
+    /// At the next occurrence of the text, which is not consumed.
+    Literal(&'static str),
+    /// At the next whitespace.
+    Whitespace,
+    /// At the end of the input.
+    End,
+}
+
+/// The trimmed text of a field and the rest of the input after it.
+fn field_text(input: &str, until: Until) -> (&str, &str) {
+    let input = input.trim_start();
+    let end = match until {
+        Until::Literal(text) => input.find(text),
+        Until::Whitespace => input.find(char::is_whitespace),
+        Until::End => None,
+    };
+    let (text, rest) = input.split_at(end.unwrap_or(input.len()));
+    (text.trim_end(), rest)
+}
+
+fn parse_field<'a, T>(name: &str, text: &'a str) -> Result<T, Failure<'a>>
+    where T: FromStr,
+          T::Err: Display
+{
+    if text.is_empty() {
+        return Err(Failure { token: text, message: format!("Missing {}", name) });
+    }
+    T::from_str(text).map_err(|e| Failure { token: text, message: format!("Invalid {}: {}", name, e) })
+}
+
+/// A field of a line pattern: the text up to `until`, parsed with [FromStr].
+pub fn field<'a, T>(name: &'static str, until: Until) -> impl Parser<'a, T>
+    where T: FromStr,
+          T::Err: Display
+{
+    move |input: &'a str| {
+        let (text, rest) = field_text(input, until);
+        parse_field(name, text).map(|value| (rest, value))
+    }
+}
+
+/// A list field of a line pattern: the text up to `until`, split at `separator` or at whitespace if
+/// there is none, with every item parsed with [FromStr].
+pub fn list<'a, T>(name: &'static str, until: Until, separator: Option<&'static str>) -> impl Parser<'a, Vec<T>>
+    where T: FromStr,
+          T::Err: Display
+{
+    move |input: &'a str| {
+        let (text, rest) = field_text(input, until);
+        let items: Vec<&str> = match separator {
+            None => text.split_whitespace().collect(),
+            Some(_) if text.is_empty() => Vec::new(),
+            Some(separator) => text.split(separator).map(str::trim).collect(),
+        };
+        let items = items.into_iter()
+            .map(|item| parse_field(name, item))
+            .collect::<Result<_, _>>()?;
+        Ok((rest, items))
+    }
+}
+
+/// Run a parser on the whole input. Anything but whitespace left over is an error.
+pub fn parse_all<'a, O>(parser: &impl Parser<'a, O>, input: &'a str) -> Result<O, ParseError> {
+    let to_error = |failure: Failure<'a>| ParseError::at(input, failure.token, failure.message);
+    let (rest, value) = parser.parse(input).map_err(to_error)?;
+    let rest = rest.trim_start();
+    if !rest.is_empty() {
+        return Err(to_error(Failure { token: token(rest), message: format!("Unexpected '{}'", token(rest)) }));
+    }
+    Ok(value)
+}
+
+/// The integer types [ints] can extract.
+pub trait Integer: FromStr<Err=ParseIntError> + Copy {
+    /// Whether a sign in front of the digits belongs to the number.
+    const SIGNED: bool;
+}
+
+macro_rules! integer {
+    ($signed:literal => $($t:ty),+) => {
+        $(impl Integer for $t {
+            const SIGNED: bool = $signed;
+        })+
+    };
+}
+
+integer!(true => i8, i16, i32, i64, i128, isize);
+integer!(false => u8, u16, u32, u64, u128, usize);
+
+/// All integers in a text with their byte spans, ignoring everything in between.
+///
+/// An integer is a run of ASCII digits. For signed types, a directly preceding `-` or `+` is part of it,
+/// like for [int]. Numbers that
+/// do not fit into `T` are errors pointing at the number. Nothing is allocated.
+pub fn ints<T: Integer>(s: &str) -> Ints<'_, T> {
+    Ints {
+        s,
+        pos: 0,
+        integer: PhantomData,
+    }
+}
+
+/// Iterator returned by [ints].
+pub struct Ints<'a, T> {
+    s: &'a str,
+    pos: usize,
+    integer: PhantomData<T>,
+}
+
+impl<T: Integer> Iterator for Ints<'_, T> {
+    type Item = Result<(T, Range<usize>), ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.s.as_bytes();
+        let digits = bytes[self.pos..].iter().position(u8::is_ascii_digit)? + self.pos;
+        let end = bytes[digits..].iter().position(|b| !b.is_ascii_digit()).map_or(bytes.len(), |len| digits + len);
+        let signed = T::SIGNED && digits > 0 && matches!(bytes[digits - 1], b'-' | b'+');
+        let start = if signed { digits - 1 } else { digits };
+        self.pos = end;
+
+        let text = &self.s[start..end];
+        Some(T::from_str(text)
+            .map(|n| (n, start..end))
+            .map_err(|e| ParseError::new(format!("Invalid number {}: {}", text, e), start..end)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn numbers() {
+        assert_eq!(uint::<u32>().parse("42 rest"), Ok((" rest", 42)));
+        assert_eq!(int::<i32>().parse("-42"), Ok(("", -42)));
+        assert_eq!(int::<i32>().parse("+7,"), Ok((",", 7)));
+        assert_eq!(uint::<i32>().parse("-42").unwrap_err().message, "Expected a number");
+        assert_eq!(int::<i32>().parse("-x").unwrap_err().token, "-");
+        assert_eq!(uint::<u32>().parse("3x").unwrap_err().token, "3x");
+        let overflow = uint::<u8>().parse("300 ").unwrap_err();
+        assert_eq!(overflow.token, "300");
+        assert_eq!(overflow.message, "Invalid number: number too large to fit in target type");
+    }
+
+    #[test]
+    fn extracts_ints() {
+        let line = "x=-12, y=+7..-3 #42";
+        let signed: Vec<_> = ints::<i64>(line).map(Result::unwrap).collect();
+        assert_eq!(signed, [(-12, 2..5), (7, 9..11), (-3, 13..15), (42, 17..19)]);
+        let unsigned: Vec<_> = ints::<u8>(line).map(|r| r.unwrap().0).collect();
+        assert_eq!(unsigned, [12, 7, 3, 42]);
+        assert_eq!(ints::<u32>("no numbers -").count(), 0);
+
+        let mut overflow = ints::<u8>("1 256 3");
+        assert!(overflow.next().unwrap().is_ok());
+        let err = overflow.next().unwrap().unwrap_err();
+        assert_eq!((err.span(), err.message()), (2..5, "Invalid number 256: number too large to fit in target type"));
+        assert_eq!(overflow.next().unwrap(), Ok((3, 6..7)));
+    }
+
+    #[test]
+    fn keywords_are_whole_words() {
+        let color = keyword([("red", 0), ("reddish", 1)]);
+        assert_eq!(color.parse("reddish,"), Ok((",", 1)));
+        assert_eq!(color.parse("red"), Ok(("", 0)));
+        let err = color.parse("redder x").unwrap_err();
+        assert_eq!((err.token, err.message.as_str()), ("redder", "Expected one of red, reddish"));
+    }
+
+    #[test]
+    fn lists() {
+        let list = separated(ws(uint::<u32>()), ws(literal(";")));
+        assert_eq!(list.parse("1 ; 2;3 x"), Ok((" x", vec![1, 2, 3])));
+        assert_eq!(list.parse("1; x").unwrap_err().token, "x");
+        assert_eq!(many(ws(uint::<u32>())).parse(" 1  2 | 3"), Ok((" | 3", vec![1, 2])));
+        assert_eq!(many(ws(uint::<u32>())).parse("|"), Ok(("|", vec![])));
+    }
+
+    #[derive(Debug, PartialEq, LineParse)]
+    #[pattern("Card {idx}: {winning} | {numbers}")]
+    struct Card {
+        idx: u32,
+        winning: Vec<u32>,
+        numbers: Vec<u8>,
+    }
+
+    #[derive(Debug, PartialEq, LineParse)]
+    #[pattern("Valve {name} has flow rate={rate}; tunnels lead to valves {tunnels, ...}")]
+    struct Valve {
+        name: String,
+        rate: i32,
+        tunnels: Vec<String>,
+    }
+
+    #[test]
+    fn derived_line_parse() {
+        assert_eq!("Card  3:  1 21 | 69 82".parse(), Ok(Card { idx: 3, winning: vec![1, 21], numbers: vec![69, 82] }));
+        assert_eq!("Card 1: | ".parse(), Ok(Card { idx: 1, winning: vec![], numbers: vec![] }));
+        assert_eq!("Valve AA has flow rate=-3; tunnels lead to valves DD, II, BB".parse(), Ok(Valve {
+            name: "AA".to_string(),
+            rate: -3,
+            tunnels: vec!["DD".to_string(), "II".to_string(), "BB".to_string()],
+        }));
+
+        let err = "Card 1: 4 5 | 6 300".parse::<Card>().unwrap_err();
+        assert_eq!((err.span(), err.message()), (16..19, "Invalid numbers: number too large to fit in target type"));
+        let err = "Card : 4 | 6".parse::<Card>().unwrap_err();
+        assert_eq!((err.span(), err.message()), (5..5, "Missing idx"));
+        let err = "Card 1: 4 5 6".parse::<Card>().unwrap_err();
+        assert_eq!((err.span(), err.message()), (13..13, "Expected '|'"));
+    }
+
+    #[test]
+    fn errors_point_at_token() {
+        let card = (ws(literal("Card")), ws(uint::<u32>()), ws(literal(":")), many(ws(uint::<u32>())));
+        let err = parse_all(&card, "Card 1: 4 5 ?").unwrap_err();
+        assert_eq!((err.span(), err.message()), (12..13, "Unexpected '?'"));
+        let err = parse_all(&card, "Card 1 4").unwrap_err();
+        assert_eq!((err.span(), err.message()), (7..8, "Expected ':'"));
+        let err = parse_all(&card, "Card").unwrap_err();
+        assert_eq!((err.span(), err.message()), (4..4, "Expected a number"));
+    }
+}
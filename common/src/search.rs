@@ -0,0 +1,282 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::collections::hash_map::Entry;
+use std::hash::Hash;
+use std::ops::Add;
+
+/// The cost of a step in a weighted search. Implemented for everything that can be added up and
+/// compared, with [Default] as zero.
+pub trait Cost: Copy + Ord + Add<Output=Self> + Default {}
+
+impl<C> Cost for C where C: Copy + Ord + Add<Output=Self> + Default {}
+
+struct Node<S, C> {
+    cost: C,
+    parent: Option<S>,
+    settled: bool,
+}
+
+/// The outcome of a search: the goal if one was reached, and the cheapest known way to every
+/// state that was visited on the way there.
+pub struct Search<S, C> {
+    goal: Option<S>,
+    nodes: HashMap<S, Node<S, C>>,
+}
+
+impl<S, C> Search<S, C>
+    where S: Clone + Eq + Hash,
+          C: Copy
+{
+    fn new() -> Self {
+        Search {
+            goal: None,
+            nodes: HashMap::new(),
+        }
+    }
+
+    /// The goal state the search stopped at.
+    pub fn goal(&self) -> Option<&S> {
+        self.goal.as_ref()
+    }
+
+    /// The cost of the cheapest path to the goal.
+    pub fn cost(&self) -> Option<C> {
+        self.cost_to(self.goal.as_ref()?)
+    }
+
+    /// The cost of the cheapest path to a visited state.
+    pub fn cost_to(&self, state: &S) -> Option<C> {
+        self.nodes.get(state)
+            .filter(|node| node.settled)
+            .map(|node| node.cost)
+    }
+
+    /// The cheapest path to the goal, from the start to the goal inclusive.
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.path_to(self.goal.as_ref()?)
+    }
+
+    /// The cheapest path to a visited state, from the start to the state inclusive.
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        let mut node = self.nodes.get(state).filter(|node| node.settled)?;
+        let mut path = vec![state.clone()];
+        while let Some(parent) = &node.parent {
+            path.push(parent.clone());
+            node = &self.nodes[parent];
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Whether the cheapest path to a state is known.
+    pub fn is_visited(&self, state: &S) -> bool {
+        self.cost_to(state).is_some()
+    }
+
+    /// All states whose cheapest path is known, in no particular order.
+    pub fn visited(&self) -> impl Iterator<Item=&S> {
+        self.nodes.iter()
+            .filter(|(_, node)| node.settled)
+            .map(|(state, _)| state)
+    }
+}
+
+/// Breadth first search from `start` until `is_goal` accepts a state. Every step costs 1.
+///
+/// Without a goal the whole reachable graph is explored, and [Search::cost_to] gives the distance
+/// to every state.
+pub fn bfs<S, N, I>(start: S, mut neighbours: N, mut is_goal: impl FnMut(&S) -> bool) -> Search<S, usize>
+    where S: Clone + Eq + Hash,
+          N: FnMut(&S) -> I,
+          I: IntoIterator<Item=S>
+{
+    let mut search = Search::new();
+    search.nodes.insert(start.clone(), Node { cost: 0, parent: None, settled: true });
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(state) = queue.pop_front() {
+        if is_goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+        let cost = search.nodes[&state].cost + 1;
+        for next in neighbours(&state) {
+            if let Entry::Vacant(entry) = search.nodes.entry(next.clone()) {
+                entry.insert(Node { cost, parent: Some(state.clone()), settled: true });
+                queue.push_back(next);
+            }
+        }
+    }
+    search
+}
+
+/// Dijkstra's algorithm from `start` until `is_goal` accepts a state. The neighbours of a state
+/// come with the cost of the step to them, which must not be negative.
+pub fn dijkstra<S, C, N, I>(start: S, neighbours: N, is_goal: impl FnMut(&S) -> bool) -> Search<S, C>
+    where S: Clone + Eq + Hash,
+          C: Cost,
+          N: FnMut(&S) -> I,
+          I: IntoIterator<Item=(S, C)>
+{
+    astar(start, neighbours, |_| C::default(), is_goal)
+}
+
+/// A* search from `start` until `is_goal` accepts a state.
+///
+/// The heuristic estimates the remaining cost to the goal. It must never overestimate, and must
+/// not drop by more than the cost of a step, otherwise the found path may not be the cheapest.
+pub fn astar<S, C, N, I>(start: S, mut neighbours: N, mut heuristic: impl FnMut(&S) -> C,
+                         mut is_goal: impl FnMut(&S) -> bool) -> Search<S, C>
+    where S: Clone + Eq + Hash,
+          C: Cost,
+          N: FnMut(&S) -> I,
+          I: IntoIterator<Item=(S, C)>
+{
+    let mut search = Search::new();
+    let mut queue = BinaryHeap::new();
+    search.nodes.insert(start.clone(), Node { cost: C::default(), parent: None, settled: false });
+    queue.push(Queued { priority: heuristic(&start), cost: C::default(), state: start });
+
+    while let Some(Queued { cost, state, .. }) = queue.pop() {
+        let Some(node) = search.nodes.get_mut(&state) else {
+            continue;
+        };
+        if node.settled || node.cost < cost {
+            continue;
+        }
+        node.settled = true;
+        if is_goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+
+        for (next, step) in neighbours(&state) {
+            let next_cost = cost + step;
+            let node = Node { cost: next_cost, parent: Some(state.clone()), settled: false };
+            match search.nodes.entry(next.clone()) {
+                Entry::Occupied(mut entry) => {
+                    if entry.get().settled || entry.get().cost <= next_cost {
+                        continue;
+                    }
+                    entry.insert(node);
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(node);
+                }
+            }
+            queue.push(Queued { priority: next_cost + heuristic(&next), cost: next_cost, state: next });
+        }
+    }
+    search
+}
+
+/// An entry of the priority queue, ordered so that the lowest priority is popped first.
+struct Queued<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> PartialEq for Queued<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Queued<S, C> {}
+
+impl<S, C: Ord> PartialOrd for Queued<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> Ord for Queued<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Input;
+    use crate::grid::{Grid, Neighbourhood, Point};
+
+    const MAZE: &str = "
+        S..#....
+        .#.#.##.
+        .#...#..
+        .####.#.
+        ......#G
+    ";
+
+    fn maze() -> (Grid<char>, Point, Point) {
+        let grid: Grid<char> = Input::new(MAZE).text().parse().unwrap();
+        let find = |target| grid.iter().find(|(_, c)| **c == target).unwrap().0;
+        (grid.clone(), find('S'), find('G'))
+    }
+
+    #[test]
+    fn bfs_finds_shortest_path() {
+        let (grid, start, goal) = maze();
+        let search = bfs(
+            start,
+            |&p| grid.passable_neighbours(p, &Neighbourhood::ORTHOGONAL, |_, c| *c != '#'),
+            |&p| p == goal,
+        );
+        assert_eq!(search.cost(), Some(15));
+        let path = search.path().unwrap();
+        assert_eq!(path.len(), 16);
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&goal));
+        assert!(path.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        let (grid, start, _) = maze();
+        let search = bfs(
+            start,
+            |&p| grid.passable_neighbours(p, &Neighbourhood::ORTHOGONAL, |_, c| *c != '#'),
+            |_| false,
+        );
+        assert_eq!(search.goal(), None);
+        assert_eq!(search.path(), None);
+        assert_eq!(search.visited().count(), grid.cells().iter().filter(|c| **c != '#').count());
+        assert!(!search.is_visited(&Point::new(3, 0)));
+    }
+
+    #[test]
+    fn weighted_search() {
+        let grid: Grid<u32> = Grid::from_vec(4, vec![
+            1, 9, 1, 1,
+            1, 9, 1, 9,
+            1, 1, 1, 9,
+            9, 9, 1, 1,
+        ]).unwrap();
+        let goal = Point::new(3, 3);
+        let neighbours = |p: &Point| grid.neighbours(*p, &Neighbourhood::ORTHOGONAL)
+            .map(|(n, cost)| (n, *cost))
+            .collect::<Vec<_>>();
+
+        let by_dijkstra = dijkstra(Point::new(0, 0), neighbours, |&p| p == goal);
+        let by_astar = astar(Point::new(0, 0), neighbours, |p| p.manhattan(goal) as u32, |&p| p == goal);
+        assert_eq!(by_dijkstra.cost(), Some(6));
+        assert_eq!(by_astar.cost(), Some(6));
+        assert_eq!(by_astar.path(), by_dijkstra.path());
+        assert!(by_astar.visited().count() <= by_dijkstra.visited().count());
+    }
+
+    #[test]
+    fn implicit_graph() {
+        // Reach 100 from 1 by doubling or adding one, where doubling is more expensive.
+        let search = dijkstra(1u32, |&n| [(n * 2, 3u32), (n + 1, 1)], |&n| n == 100);
+        assert_eq!(search.cost(), Some(18));
+        let path = search.path().unwrap();
+        let steps: u32 = path.windows(2)
+            .map(|w| if w[1] == w[0] + 1 { 1 } else { 3 })
+            .sum();
+        assert_eq!((path[0], path[path.len() - 1], steps), (1, 100, 18));
+    }
+}
@@ -0,0 +1,227 @@
+//! Derive macros for the shared library. They are re-exported by `common`, use them from there.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, GenericArgument, LitStr, PathArguments, Type};
+
+/// One piece of a line pattern.
+#[derive(Debug, PartialEq, Eq)]
+enum Segment {
+    /// Text that has to appear in the line. Whitespace around it is ignored.
+    Literal(String),
+    /// A `{name}` placeholder, or `{name...}` and `{name<separator>...}` for lists.
+    Field {
+        name: String,
+        list: bool,
+        separator: Option<String>,
+    },
+}
+
+/// Split a pattern like `Card {idx}: {winning...} | {numbers...}` into segments.
+fn parse_pattern(pattern: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut rest = pattern;
+    while let Some(open) = rest.find('{') {
+        if open > 0 {
+            segments.push(Segment::Literal(rest[..open].to_string()));
+        } else if matches!(segments.last(), Some(Segment::Field { .. })) {
+            return Err("Fields must be separated by text".to_string());
+        }
+        let close = rest[open..].find('}')
+            .ok_or_else(|| "Unclosed '{'".to_string())? + open;
+        let placeholder = &rest[open + 1..close];
+        let name_len = placeholder.find(|c: char| !c.is_alphanumeric() && c != '_').unwrap_or(placeholder.len());
+        let (name, suffix) = placeholder.split_at(name_len);
+        if name.is_empty() {
+            return Err(format!("Missing field name in '{{{}}}'", placeholder));
+        }
+        let (list, separator) = match suffix.strip_suffix("...") {
+            None if suffix.is_empty() => (false, None),
+            None => return Err(format!("Invalid field '{{{}}}'. Lists end with '...'.", placeholder)),
+            Some(separator) if separator.trim().is_empty() => (true, None),
+            Some(separator) => (true, Some(separator.trim().to_string())),
+        };
+        segments.push(Segment::Field { name: name.to_string(), list, separator });
+        rest = &rest[close + 1..];
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Literal(rest.to_string()));
+    }
+    Ok(segments)
+}
+
+/// The element type if `ty` is a `Vec`.
+fn vec_element(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let last = path.path.segments.last()?;
+    if last.ident != "Vec" {
+        return None;
+    }
+    match &last.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first()? {
+            GenericArgument::Type(element) => Some(element),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Derive `FromStr` for a struct from a pattern describing one line.
+///
+/// ```ignore
+/// #[derive(LineParse)]
+/// #[pattern("Card {idx}: {winning_numbers} | {numbers}")]
+/// struct Card {
+///     idx: u32,
+///     winning_numbers: Vec<u32>,
+///     numbers: Vec<u32>,
+/// }
+/// ```
+///
+/// Every field of the struct appears in the pattern exactly once, as `{field}`. A field extends up to
+/// the text that follows it in the pattern and is parsed with its `FromStr` implementation. Fields of
+/// type `Vec` are lists that are split at whitespace, or at a separator given as `{field;...}`.
+/// Whitespace around the text between fields does not have to match exactly.
+///
+/// Errors are `common::ParseError`s that point at the field or list item that could not be parsed.
+#[proc_macro_derive(LineParse, attributes(pattern))]
+pub fn derive_line_parse(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    line_parse(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+fn line_parse(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let error = |message: &str| syn::Error::new_spanned(ident, message);
+
+    if !input.generics.params.is_empty() {
+        return Err(error("LineParse does not support generic structs"));
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(error("LineParse requires a struct with named fields")),
+        },
+        _ => return Err(error("LineParse can only be derived for structs")),
+    };
+    let attr = input.attrs.iter()
+        .find(|attr| attr.path().is_ident("pattern"))
+        .ok_or_else(|| error("Missing #[pattern(\"...\")] attribute"))?;
+    let pattern: LitStr = attr.parse_args()?;
+    let segments = parse_pattern(&pattern.value())
+        .map_err(|message| syn::Error::new_spanned(&pattern, message))?;
+
+    for field in fields {
+        let name = field.ident.as_ref().expect("Named field").to_string();
+        let uses = segments.iter().filter(|s| matches!(s, Segment::Field { name: n, .. } if *n == name)).count();
+        if uses != 1 {
+            let message = format!("Field '{}' has to appear in the pattern exactly once", name);
+            return Err(syn::Error::new_spanned(field, message));
+        }
+    }
+
+    let mut steps = Vec::new();
+    for (idx, segment) in segments.iter().enumerate() {
+        match segment {
+            Segment::Literal(text) => {
+                let text = text.trim();
+                if !text.is_empty() {
+                    steps.push(quote! {
+                        let (input, ()) = ::common::parse::Parser::parse(
+                            &::common::parse::ws(::common::parse::literal(#text)), input)?;
+                    });
+                }
+            }
+            Segment::Field { name, list, separator } => {
+                let field = fields.iter()
+                    .find(|f| f.ident.as_ref().is_some_and(|i| i == name))
+                    .ok_or_else(|| syn::Error::new_spanned(&pattern, format!("Unknown field '{}'", name)))?;
+                let field_ident = field.ident.as_ref().expect("Named field");
+                let until = match segments.get(idx + 1) {
+                    Some(Segment::Literal(text)) if text.trim().is_empty() => {
+                        if separator.is_none() && (*list || vec_element(&field.ty).is_some()) {
+                            let message = format!("The list '{}' has to be followed by text other than whitespace", name);
+                            return Err(syn::Error::new_spanned(&pattern, message));
+                        }
+                        quote!(::common::parse::Until::Whitespace)
+                    }
+                    Some(Segment::Literal(text)) => {
+                        let text = text.trim();
+                        quote!(::common::parse::Until::Literal(#text))
+                    }
+                    _ => quote!(::common::parse::Until::End),
+                };
+                let parser = match vec_element(&field.ty) {
+                    Some(element) => {
+                        let separator = match separator {
+                            Some(separator) => quote!(::std::option::Option::Some(#separator)),
+                            None => quote!(::std::option::Option::None),
+                        };
+                        quote!(::common::parse::list::<#element>(#name, #until, #separator))
+                    }
+                    None if *list => {
+                        let message = format!("The list '{}' has to be a Vec", name);
+                        return Err(syn::Error::new_spanned(field, message));
+                    }
+                    None => {
+                        let ty = &field.ty;
+                        quote!(::common::parse::field::<#ty>(#name, #until))
+                    }
+                };
+                steps.push(quote! {
+                    let (input, #field_ident) = ::common::parse::Parser::parse(&#parser, input)?;
+                });
+            }
+        }
+    }
+
+    let field_idents = fields.iter().map(|f| f.ident.as_ref());
+    Ok(quote! {
+        impl ::std::str::FromStr for #ident {
+            type Err = ::common::ParseError;
+
+            fn from_str(line: &str) -> ::std::result::Result<Self, Self::Err> {
+                fn parser(input: &str) -> ::common::parse::PResult<'_, #ident> {
+                    #(#steps)*
+                    ::std::result::Result::Ok((input, #ident { #(#field_idents),* }))
+                }
+                ::common::parse::parse_all(&parser, line)
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn field(name: &str, list: bool, separator: Option<&str>) -> Segment {
+        Segment::Field { name: name.to_string(), list, separator: separator.map(str::to_string) }
+    }
+
+    #[test]
+    fn parses_pattern() {
+        assert_eq!(parse_pattern("Game {id}: {rounds; ...}").unwrap(), [
+            Segment::Literal("Game ".to_string()),
+            field("id", false, None),
+            Segment::Literal(": ".to_string()),
+            field("rounds", true, Some(";")),
+        ]);
+        assert_eq!(parse_pattern("{a} -> {b...}").unwrap(), [
+            field("a", false, None),
+            Segment::Literal(" -> ".to_string()),
+            field("b", true, None),
+        ]);
+    }
+
+    #[test]
+    fn invalid_patterns() {
+        assert!(parse_pattern("{a}{b}").is_err());
+        assert!(parse_pattern("{a").is_err());
+        assert!(parse_pattern("{}").is_err());
+        assert!(parse_pattern("{a;}").is_err());
+    }
+}
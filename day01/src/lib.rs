@@ -0,0 +1,171 @@
+use itertools::Itertools;
+use common::{checked_sum, Input, ParseError, Solution, SolutionError};
+
+static DIGITS: [&str; 18] = [
+    "1",
+    "2",
+    "3",
+    "4",
+    "5",
+    "6",
+    "7",
+    "8",
+    "9",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+];
+
+/// A line of the calibration document.
+pub struct Line {
+    /// 1-based line number
+    number: usize,
+    text: String,
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    type Input = Vec<Line>;
+    type Answer = u32;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, SolutionError> {
+        Ok(Input::new(input).lines()
+            .map(|(number, text)| Line {
+                number,
+                text: text.to_owned(),
+            })
+            .collect())
+    }
+
+    fn part_one(&self, lines: &Self::Input) -> Result<Self::Answer, SolutionError> {
+        trebuchet_value(lines, calibration_value)
+    }
+
+    fn part_two(&self, lines: &Self::Input) -> Result<Self::Answer, SolutionError> {
+        trebuchet_value(lines, spelled_calibration_value)
+    }
+}
+
+/// Sum up the calibration values of all lines. Fails on the first line that has no digit.
+fn trebuchet_value(lines: &[Line], calibration_value: fn(&str) -> Option<u32>) -> Result<u32, SolutionError> {
+    let mut sum: u32 = 0;
+    for line in lines {
+        let text = line.text.as_str();
+        let value = calibration_value(text)
+            .ok_or_else(|| ParseError::at(text, text.trim(), "Line contains no digit").in_line(line.number, text, text))?;
+        sum = checked_sum(sum, value)?;
+    }
+    Ok(sum)
+}
+
+/// The calibration value made from the first and last digit in the line.
+fn calibration_value(line: &str) -> Option<u32> {
+
+    let first = first_digit(line.chars());
+    let last = first_digit(line.chars().rev());
+
+    first.zip(last)
+        .map(|(a, b)| a * 10 + b)
+}
+
+/// Get the first digit in a char iterator as an u32, if it exists.
+fn first_digit(mut it: impl Iterator<Item=char>) -> Option<u32> {
+
+    let is_digit = | c: &char | -> bool {
+        c.is_ascii_digit()
+    };
+    let to_u32 = | c: char | -> Option<u32> {
+        c.to_digit(10)
+    };
+
+    it.find(is_digit).and_then(to_u32)
+}
+
+/// The calibration value made from the first and last digit in the line, where digits may also be
+/// spelled out with letters.
+fn spelled_calibration_value(line: &str) -> Option<u32> {
+
+    // Map the index into the DIGITS array to the numerical digit value
+    let from_regex_index = |mut i: usize| -> u32 {
+        if i >= 9 {
+            i -= 9;
+        }
+        (i  + 1) as u32
+    };
+
+    // Find all occurrences of the pattern.
+    // Returns an iterable of (str_idx, pattern_idx)
+    let find_pattern = |(pattern_idx, pattern)| {
+        line.match_indices(pattern)
+            .map(move |(i, _)| (i, pattern_idx))
+    };
+
+    let mut it = DIGITS.iter().enumerate()
+        // Find all indices where this digit string occurs
+        .flat_map(find_pattern)
+        // Sort the matches by ascending index in the line string
+        .sorted_by_key(|(i, _)| *i)
+        // Discard the line index and map the pattern index to the digit
+        .map(|(_, s)| from_regex_index(s));
+
+    let first = it.next();
+    let last = it.next_back().or(first);
+    first.zip(last)
+        .map(|(a, b)| a * 10 + b)
+}
+
+#[cfg(test)]
+mod test {
+    use common::Part;
+    use super::*;
+
+    #[test]
+    fn simple_case_part_one() {
+        let input = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet";
+        assert_eq!(Day01.solve(Part::One, input).unwrap(), 142);
+    }
+
+    #[test]
+    fn simple_case_part_two() {
+        let input = r#"
+            two1nine
+            eightwothree
+            abcone2threexyz
+            xtwone3four
+            4nineeightseven2
+            zoneight234
+            7pqrstsixteen
+        "#;
+        assert_eq!(Day01.solve(Part::Two, input).unwrap(), 281);
+    }
+
+    #[test]
+    fn line_without_digit() {
+        let input = "1abc2\n\n  abc\n";
+        let err = Day01.parse(input).and_then(|lines| Day01.part_one(&lines)).err().unwrap();
+        let SolutionError::Parse(err) = err else { panic!("Expected a parse error") };
+        assert_eq!(err.line(), Some(3));
+        assert_eq!(err.span(), 2..5);
+    }
+
+    #[test]
+    fn overflow_is_an_error() {
+        let lines = Day01.parse("a\nb").unwrap();
+        let result = trebuchet_value(&lines, |_| Some(u32::MAX));
+        assert!(matches!(result, Err(SolutionError::NoAnswer(_))));
+    }
+
+    #[test]
+    fn problematic_line_01() {
+        let input = "pxvmbjprllmbfpzjxsvhc5";
+        assert_eq!(Day01.solve(Part::Two, input).unwrap(), 55);
+    }
+}
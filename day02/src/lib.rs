@@ -0,0 +1,235 @@
+use std::io::BufRead;
+use std::str::FromStr;
+use common::{checked_sum, records, Input, ParseError, Part, Solution, SolutionError};
+use common::parse::{keyword, literal, parse_all, separated, uint, ws, Parser};
+
+struct Quantities {
+    reds: u32,
+    greens: u32,
+    blues: u32,
+}
+
+impl Quantities {
+    /// Returns true iff all quantities inside self are within the limits set by other.
+    fn fits_inside(&self, other: &Quantities) -> bool {
+        self.reds <= other.reds && self.greens <= other.greens && self.blues <= other.blues
+    }
+}
+
+/// The cubes in the bag for part one.
+const AVAILABLE: Quantities = Quantities {
+    reds: 12,
+    greens: 13,
+    blues: 14,
+};
+
+pub struct Game {
+    id: u32,
+    rounds: Vec<Round>,
+}
+
+/// The cubes shown in one round, in the order they are listed. A color may be listed more than once.
+struct Round {
+    draws: Vec<(u32, Color)>,
+}
+
+#[derive(Clone, Copy)]
+enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+impl FromStr for Game {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let color = keyword([("red", Color::Red), ("green", Color::Green), ("blue", Color::Blue)]);
+        let quantity = (ws(uint::<u32>()), ws(color));
+        let round = separated(quantity, ws(literal(","))).map(|draws| Round { draws });
+        let game = (ws(literal("Game")), ws(uint()), ws(literal(":")), separated(round, ws(literal(";"))))
+            .map(|(_, id, _, rounds)| Game { id, rounds });
+        parse_all(&game, line)
+    }
+}
+
+impl Game {
+    /// The total number of cubes of each color in every round.
+    fn round_quantities(&self) -> impl Iterator<Item=Result<Quantities, SolutionError>> + '_ {
+        self.rounds.iter().map(|round| {
+            let mut quantities = Quantities { reds: 0, greens: 0, blues: 0 };
+            for &(num, color) in &round.draws {
+                let total = match color {
+                    Color::Red => &mut quantities.reds,
+                    Color::Green => &mut quantities.greens,
+                    Color::Blue => &mut quantities.blues,
+                };
+                *total = total.checked_add(num)
+                    .ok_or_else(|| SolutionError::NoAnswer(format!("Game {} shows too many cubes in a round", self.id)))?;
+            }
+            Ok(quantities)
+        })
+    }
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    type Input = Vec<Game>;
+    type Answer = u32;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, SolutionError> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_one(&self, games: &Self::Input) -> Result<Self::Answer, SolutionError> {
+        sum_possible_ids(games, &AVAILABLE)
+    }
+
+    fn part_two(&self, games: &Self::Input) -> Result<Self::Answer, SolutionError> {
+        sum_of_powers(games)
+    }
+
+    fn solve_stream(&self, part: Part, input: &mut dyn BufRead) -> Option<Result<Self::Answer, SolutionError>> {
+        let value: fn(&Game) -> Result<u32, SolutionError> = match part {
+            Part::One => possible_id,
+            Part::Two => power,
+        };
+        Some(records(input).try_fold(0, |sum, record| {
+            let (_, game) = record?;
+            checked_sum(sum, value(&game)?)
+        }))
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<Game>, ParseError> {
+    Input::new(input).parse_lines()
+}
+
+fn sum_possible_ids(games: &[Game], available: &Quantities) -> Result<u32, SolutionError> {
+    let mut sum: u32 = 0;
+    for game in games {
+        if is_game_possible(game, available)? {
+            sum = checked_sum(sum, game.id)?;
+        }
+    }
+    Ok(sum)
+}
+
+/// The id of a game that is possible with the cubes available in part one, 0 otherwise.
+fn possible_id(game: &Game) -> Result<u32, SolutionError> {
+    Ok(if is_game_possible(game, &AVAILABLE)? { game.id } else { 0 })
+}
+
+fn is_game_possible(game: &Game, quantities: &Quantities) -> Result<bool, SolutionError> {
+    for round in game.round_quantities() {
+        if !round?.fits_inside(quantities) {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+fn sum_of_powers(games: &[Game]) -> Result<u32, SolutionError> {
+    games.iter().try_fold(0, |sum, game| checked_sum(sum, power(game)?))
+}
+
+fn power(game: &Game) -> Result<u32, SolutionError> {
+    let quantities = minimum_quantities(game)?;
+    quantities.reds.checked_mul(quantities.greens)
+        .and_then(|p| p.checked_mul(quantities.blues))
+        .ok_or_else(|| SolutionError::NoAnswer(format!("The power of game {} overflows", game.id)))
+}
+
+fn minimum_quantities(game: &Game) -> Result<Quantities, SolutionError> {
+
+    let mut min_r = 0;
+    let mut min_g = 0;
+    let mut min_b = 0;
+
+    for round in game.round_quantities() {
+        let round = round?;
+        if round.reds > min_r {
+            min_r = round.reds;
+        }
+        if round.greens > min_g {
+            min_g = round.greens;
+        }
+        if round.blues > min_b {
+            min_b = round.blues;
+        }
+    }
+
+    Ok(Quantities {
+        reds: min_r,
+        greens: min_g,
+        blues: min_b,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = r#"
+            Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+            Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+            Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+            Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+            Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+        "#;
+
+    #[test]
+    fn simple_case_part_one() {
+        let available = Quantities {
+            reds: 12,
+            greens: 13,
+            blues: 14,
+        };
+        let games = parse_input(EXAMPLE).unwrap();
+        assert_eq!(sum_possible_ids(&games, &available).unwrap(), 8);
+    }
+
+    #[test]
+    fn simple_case_part_two() {
+        assert_eq!(Day02.solve(Part::Two, EXAMPLE).unwrap(), 2286);
+    }
+
+    #[test]
+    fn streaming_matches_parsed() {
+        for part in [Part::One, Part::Two] {
+            let streamed = Day02.solve_stream(part, &mut EXAMPLE.as_bytes()).unwrap();
+            assert_eq!(streamed, Day02.solve(part, EXAMPLE));
+        }
+        let streamed = Day02.solve_stream(Part::One, &mut "Game 1: 3 blue\nGame x: 1 red\n".as_bytes()).unwrap();
+        assert!(matches!(streamed, Err(SolutionError::Parse(e)) if e.line() == Some(2)));
+    }
+
+    #[test]
+    fn streaming_overflow_is_an_error() {
+        let input = "Game 4294967295: 1 red\nGame 4294967295: 1 red\n";
+        let streamed = Day02.solve_stream(Part::One, &mut input.as_bytes()).unwrap();
+        assert!(matches!(streamed, Err(SolutionError::NoAnswer(_))));
+        let input = "Game 1: 2000 red, 2000 green, 1000 blue\n".repeat(2);
+        let streamed = Day02.solve_stream(Part::Two, &mut input.as_bytes()).unwrap();
+        assert!(matches!(streamed, Err(SolutionError::NoAnswer(_))));
+    }
+
+    #[test]
+    fn overflow_is_an_error() {
+        let input = "Game 1: 4294967295 red, 1 red";
+        assert!(matches!(Day02.solve(Part::One, input), Err(SolutionError::NoAnswer(_))));
+        let input = "Game 1: 2000 red, 2000 green, 2000 blue";
+        assert!(matches!(Day02.solve(Part::Two, input), Err(SolutionError::NoAnswer(_))));
+    }
+
+    #[test]
+    fn error_points_at_token() {
+        let input = "Game 1: 3 blue\n  Game 2: 1 blue, 2 purple\n";
+        let err = parse_input(input).err().unwrap();
+        assert_eq!(err.line(), Some(2));
+        assert_eq!(err.column(), Some(21));
+        assert_eq!(err.span(), 20..26);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,189 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+use common::{checked_sum, Input, ParseError, Solution, SolutionError};
+use common::grid::{Axis, Grid, Neighbourhood, Point, Run, SparseGrid};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    type Input = Engine;
+    type Answer = u32;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, SolutionError> {
+        Ok(Engine::from_str(input)?)
+    }
+
+    fn part_one(&self, engine: &Self::Input) -> Result<Self::Answer, SolutionError> {
+        sum_part_numbers(engine)
+    }
+
+    fn part_two(&self, engine: &Self::Input) -> Result<Self::Answer, SolutionError> {
+        sum_gear_ratios(engine)
+    }
+}
+
+struct PartNumber {
+    num: u32,
+    /// Positions of all adjacent symbols
+    adjacent_symbols: HashSet<Point>,
+}
+
+pub struct Engine {
+    numbers: Vec<PartNumber>,
+    symbols: SparseGrid<char>,
+}
+
+impl FromStr for Engine {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+
+        let input = Input::new(s);
+        // Row y of the grid is the line rows[y]
+        let rows: Vec<(usize, &str)> = input.lines().collect();
+        let text = rows.iter().map(|(_, row)| *row).collect::<Vec<_>>().join("\n");
+        let grid: Grid<char> = text.parse().map_err(|e: ParseError| {
+            let number = e.line().map_or(0, |row| rows[row - 1].0);
+            input.in_line(e, number)
+        })?;
+        let mut numbers = Vec::new();
+
+        for (run, num) in grid.numbers(Axis::Rows) {
+            let num = num.map_err(|e| input.in_line(e, rows[run.start.y as usize].0))?;
+            numbers.push(PartNumber {
+                num,
+                adjacent_symbols: adjacent_symbol_positions(&run, &grid),
+            });
+        }
+
+        Ok(Engine {
+            numbers,
+            symbols: SparseGrid::from_grid(&grid, |_, c| is_symbol(*c)),
+        })
+    }
+}
+
+fn is_symbol(c: char) -> bool {
+    !c.is_ascii_digit() && c != '.' && !c.is_whitespace()
+}
+
+/// Get the positions of all symbols around the range of a number
+fn adjacent_symbol_positions(run: &Run, grid: &Grid<char>) -> HashSet<Point> {
+    let mut res = HashSet::new();
+    for cell in run.points() {
+        for (p, adjacent) in grid.neighbours(cell, &Neighbourhood::ALL) {
+            if is_symbol(*adjacent) {
+                res.insert(p);
+            }
+        }
+    }
+    res
+}
+
+fn sum_part_numbers(engine: &Engine) -> Result<u32, SolutionError> {
+    engine.numbers.iter()
+        .filter(|n| !n.adjacent_symbols.is_empty())
+        .try_fold(0, |sum, n| checked_sum(sum, n.num))
+}
+
+fn sum_gear_ratios(engine: &Engine) -> Result<u32, SolutionError> {
+    let mut sum = 0;
+
+    for (cog_pos, _) in engine.symbols.iter().filter(|(_, sym)| **sym == '*') {
+        let mut adjacent_numbers = Vec::new();
+        for number in &engine.numbers {
+            if number.adjacent_symbols.contains(&cog_pos) {
+                adjacent_numbers.push(number.num);
+            }
+        }
+
+        if let [a, b] = adjacent_numbers[..] {
+            let ratio = a.checked_mul(b)
+                .ok_or_else(|| SolutionError::NoAnswer(format!("The gear ratio at {} overflows", cog_pos)))?;
+            sum = checked_sum(sum, ratio)?;
+        }
+    }
+    Ok(sum)
+}
+
+#[cfg(test)]
+mod test {
+    use common::Part;
+    use super::*;
+
+    #[test]
+    fn simple_case_part_one() {
+        let input = r#"
+            467..114..
+            ...*......
+            ..35..633.
+            ......#...
+            617*......
+            .....+.58.
+            ..592.....
+            ......755.
+            ...$.*....
+            .664.598..
+        "#;
+        assert_eq!(Day03.solve(Part::One, input).unwrap(), 4361);
+    }
+
+    #[test]
+    fn problematic_case_01() {
+        let input = r#"*920"#;
+        assert_eq!(Day03.solve(Part::One, input).unwrap(), 920);
+    }
+
+    #[test]
+    fn inconsistent_line_lengths() {
+        let input = "467..114..\n...*......\n..35..633\n";
+        let err = Engine::from_str(input).err().unwrap();
+        assert_eq!(err.line(), Some(3));
+        assert_eq!(err.span(), 0..9);
+    }
+
+    #[test]
+    fn overflow_is_an_error() {
+        assert!(matches!(Day03.solve(Part::One, "4294967295*1"), Err(SolutionError::NoAnswer(_))));
+        assert!(matches!(Day03.solve(Part::Two, "99999*99999"), Err(SolutionError::NoAnswer(_))));
+    }
+
+    #[test]
+    fn indentation_is_not_a_symbol() {
+        assert_eq!(Day03.solve(Part::One, "\n    12..\n    ....\n  ").unwrap(), 0);
+        assert_eq!(Day03.solve(Part::One, "12 .\n...."), Ok(0));
+    }
+
+    #[test]
+    fn indented_errors_point_at_line() {
+        let err = Engine::from_str("\n    467...........\n\n    ..99999999999*\n").err().unwrap();
+        assert_eq!((err.line(), err.column()), (Some(4), Some(7)));
+        let err = Engine::from_str("\n    467..\n    .*.\n").err().unwrap();
+        assert_eq!((err.line(), err.column()), (Some(3), Some(5)));
+    }
+
+    #[test]
+    fn number_error_points_at_line() {
+        let input = "\n\n..99999999999*\n..............\n";
+        let err = Engine::from_str(input).err().unwrap();
+        assert_eq!((err.line(), err.column()), (Some(3), Some(3)));
+        assert_eq!(err.span(), 2..13);
+    }
+
+    #[test]
+    fn simple_case_part_two() {
+        let input = r#"467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+"#;
+        assert_eq!(Day03.solve(Part::Two, input).unwrap(), 467835);
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::VecDeque;
+use std::io::BufRead;
+use common::{checked_sum, records, Input, ParseError, Part, Solution, SolutionError};
+use common::parse::LineParse;
+
+#[derive(Clone, LineParse)]
+#[pattern("Card {idx}: {winning_numbers} | {numbers}")]
+pub struct Card {
+    pub idx: u32,
+    pub winning_numbers: Vec<u32>,
+    pub numbers: Vec<u32>,
+}
+
+impl Card {
+    /// The number of the card's numbers that are also winning numbers.
+    fn num_wins(&self) -> u32 {
+        self.numbers.iter()
+            .filter(|n| self.winning_numbers.contains(*n))
+            .count() as u32
+    }
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    type Input = Vec<Card>;
+    type Answer = u32;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, SolutionError> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_one(&self, cards: &Self::Input) -> Result<Self::Answer, SolutionError> {
+        total_points(cards)
+    }
+
+    fn part_two(&self, cards: &Self::Input) -> Result<Self::Answer, SolutionError> {
+        total_cards(cards)
+    }
+
+    fn solve_stream(&self, part: Part, input: &mut dyn BufRead) -> Option<Result<Self::Answer, SolutionError>> {
+        let mut cards = records::<_, Card>(input).map(|record| record.map(|(_, card)| card));
+        Some(match part {
+            Part::One => cards.try_fold(0, |sum, card| add_points(sum, &card?)),
+            Part::Two => cards.try_fold(CardCounter::default(), |mut counter, card| {
+                counter.add(&card?)?;
+                Ok(counter)
+            }).map(|counter| counter.total),
+        })
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<Card>, ParseError> {
+    Input::new(input).parse_lines()
+}
+
+fn total_points(cards: &[Card]) -> Result<u32, SolutionError> {
+    cards.iter().try_fold(0, add_points)
+}
+
+/// Add the points a card is worth to the sum.
+fn add_points(sum: u32, card: &Card) -> Result<u32, SolutionError> {
+    let num_wins = card.num_wins();
+    if num_wins == 0 {
+        return Ok(sum);
+    }
+    let points = (2u32).checked_pow(num_wins - 1)
+        .ok_or_else(|| SolutionError::NoAnswer(format!("Card {} is worth too many points", card.idx)))?;
+    checked_sum(sum, points)
+}
+
+fn total_cards(cards: &[Card]) -> Result<u32, SolutionError> {
+    let mut counter = CardCounter::default();
+    for card in cards {
+        counter.add(card)?;
+    }
+    Ok(counter.total)
+}
+
+/// Counts the cards of part two while going through the original cards in order.
+#[derive(Default)]
+struct CardCounter {
+    /// The copies won so far of the upcoming cards, starting with the next one.
+    pending: VecDeque<u32>,
+    total: u32,
+}
+
+impl CardCounter {
+    fn add(&mut self, card: &Card) -> Result<(), SolutionError> {
+        let too_many = || SolutionError::NoAnswer(format!("Too many copies of the cards after card {}", card.idx));
+        let copies = self.pending.pop_front().unwrap_or(0).checked_add(1).ok_or_else(too_many)?;
+        let wins = card.num_wins() as usize;
+        if self.pending.len() < wins {
+            self.pending.resize(wins, 0);
+        }
+        for pending in self.pending.iter_mut().take(wins) {
+            *pending = pending.checked_add(copies).ok_or_else(too_many)?;
+        }
+        self.total = checked_sum(self.total, copies)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = r#"
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+        "#;
+
+    #[test]
+    fn simple_case_part_one() {
+        assert_eq!(Day04.solve(Part::One, EXAMPLE).unwrap(), 13);
+    }
+
+    #[test]
+    fn simple_case_part_two() {
+        assert_eq!(Day04.solve(Part::Two, EXAMPLE).unwrap(), 30);
+    }
+
+    #[test]
+    fn streaming_matches_parsed() {
+        for part in [Part::One, Part::Two] {
+            let streamed = Day04.solve_stream(part, &mut EXAMPLE.as_bytes()).unwrap();
+            assert_eq!(streamed, Day04.solve(part, EXAMPLE));
+        }
+    }
+
+    #[test]
+    fn error_points_at_token() {
+        let input = "Card 1: 41 48 | 83 86\nCard 2: 13 3x | 61 30\n";
+        let err = parse_input(input).err().unwrap();
+        assert_eq!(err.line(), Some(2));
+        assert_eq!(err.span(), 11..13);
+        assert!(err.render().ends_with("2 | Card 2: 13 3x | 61 30\n  |            ^^"));
+    }
+
+    #[test]
+    fn too_many_points() {
+        let winning = (1..=40).map(|n| n.to_string()).collect::<Vec<_>>().join(" ");
+        let input = format!("Card 1: {} | {}", winning, winning);
+        assert!(matches!(Day04.solve(Part::One, &input), Err(SolutionError::NoAnswer(_))));
+    }
+
+    /// Every card wins all of the cards after it, which doubles the copies with every card.
+    fn doubling_cards(count: u32) -> String {
+        (1..=count)
+            .map(|idx| {
+                let numbers = (1..=count + 1 - idx).map(|n| n.to_string()).collect::<Vec<_>>().join(" ");
+                format!("Card {}: {} | {}\n", idx, numbers, numbers)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn too_many_cards() {
+        assert_eq!(Day04.solve(Part::Two, &doubling_cards(8)).unwrap(), 255);
+        assert!(matches!(Day04.solve(Part::Two, &doubling_cards(33)), Err(SolutionError::NoAnswer(_))));
+    }
+
+    #[test]
+    fn streaming_overflow_is_an_error() {
+        let streamed = Day04.solve_stream(Part::Two, &mut doubling_cards(33).as_bytes()).unwrap();
+        assert!(matches!(streamed, Err(SolutionError::NoAnswer(_))));
+        let winning = (1..=40).map(|n| n.to_string()).collect::<Vec<_>>().join(" ");
+        let input = format!("Card 1: {} | {}\n", winning, winning);
+        let streamed = Day04.solve_stream(Part::One, &mut input.as_bytes()).unwrap();
+        assert!(matches!(streamed, Err(SolutionError::NoAnswer(_))));
+    }
+}
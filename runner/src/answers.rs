@@ -0,0 +1,114 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+use anyhow::Context;
+use common::Part;
+use serde::{Deserialize, Serialize};
+
+/// Accepted answers, stored in a TOML file keyed by day and part:
+///
+/// ```toml
+/// [day03]
+/// part1 = "4361"
+/// part2 = "467835"
+/// ```
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Answers(BTreeMap<String, BTreeMap<String, String>>);
+
+/// The result of comparing an answer against the accepted one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Correct,
+    Wrong { expected: String },
+    Unknown,
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Check::Correct => f.pad("ok"),
+            Check::Wrong { expected } => f.pad(&format!("WRONG, expected {}", expected)),
+            Check::Unknown => f.pad("new"),
+        }
+    }
+}
+
+fn day_key(day: u8) -> String {
+    format!("day{:02}", day)
+}
+
+fn part_key(part: Part) -> String {
+    format!("part{}", part)
+}
+
+impl Answers {
+
+    /// Load the answers from a file. A missing file is an empty database.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Answers::default());
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read answers from {}", path.display()))?;
+        toml::from_str(&content)
+            .with_context(|| format!("Invalid answers file {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let content = toml::to_string(self)?;
+        fs::write(path, content)
+            .with_context(|| format!("Failed to write answers to {}", path.display()))
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.0.get(&day_key(day))
+            .and_then(|parts| parts.get(&part_key(part)))
+            .map(String::as_str)
+    }
+
+    /// Record an answer as accepted. Returns false if there already was an accepted answer.
+    pub fn accept(&mut self, day: u8, part: Part, answer: &str) -> bool {
+        let parts = self.0.entry(day_key(day)).or_default();
+        if parts.contains_key(&part_key(part)) {
+            return false;
+        }
+        parts.insert(part_key(part), answer.to_owned());
+        true
+    }
+
+    pub fn check(&self, day: u8, part: Part, answer: &str) -> Check {
+        match self.get(day, part) {
+            Some(expected) if expected == answer => Check::Correct,
+            Some(expected) => Check::Wrong { expected: expected.to_owned() },
+            None => Check::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn check_against_accepted() {
+        let mut answers: Answers = toml::from_str("[day03]\npart1 = \"4361\"\n").unwrap();
+        assert_eq!(answers.check(3, Part::One, "4361"), Check::Correct);
+        assert_eq!(answers.check(3, Part::One, "4360"), Check::Wrong { expected: "4361".to_owned() });
+        assert_eq!(answers.check(3, Part::Two, "467835"), Check::Unknown);
+
+        assert!(answers.accept(3, Part::Two, "467835"));
+        assert!(!answers.accept(3, Part::One, "1"));
+        assert_eq!(answers.check(3, Part::Two, "467835"), Check::Correct);
+        assert_eq!(answers.get(3, Part::One), Some("4361"));
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut answers = Answers::default();
+        answers.accept(1, Part::One, "142");
+        let s = toml::to_string(&answers).unwrap();
+        assert_eq!(s, "[day01]\npart1 = \"142\"\n");
+    }
+}
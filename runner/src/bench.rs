@@ -0,0 +1,201 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use anyhow::{anyhow, Context};
+use clap::Args as ClapArgs;
+use common::Part;
+use serde::{Deserialize, Serialize};
+use crate::{registry, Args};
+
+#[derive(ClapArgs)]
+pub struct BenchArgs {
+    /// How often each phase is run.
+    #[arg(long, default_value_t = 100)]
+    iterations: u32,
+
+    /// The JSON file holding the baseline timings to compare against.
+    #[arg(long, default_value = "bench_baseline.json")]
+    baseline: PathBuf,
+
+    /// Save the timings of this run as the new baseline.
+    #[arg(long)]
+    save_baseline: bool,
+
+    /// Report a regression when the median is this many percent slower than the baseline.
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64,
+}
+
+/// A phase of a solution that is timed on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Parse,
+    Solve(Part),
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => f.pad("parse"),
+            Phase::Solve(part) => f.pad(&format!("part{}", part)),
+        }
+    }
+}
+
+/// Timing statistics of a phase in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub p95_ns: u64,
+}
+
+impl Stats {
+
+    /// Compute the statistics of a non-empty set of samples.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "Cannot compute statistics without samples");
+        samples.sort();
+        let percentile = |p: usize| samples[(samples.len() - 1) * p / 100].as_nanos() as u64;
+        Stats {
+            min_ns: percentile(0),
+            median_ns: percentile(50),
+            p95_ns: percentile(95),
+        }
+    }
+}
+
+/// Timings of all benchmarked phases, keyed by `dayNN/phase`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Baseline(BTreeMap<String, Stats>);
+
+impl Baseline {
+
+    /// Load a baseline from a file. A missing file is an empty baseline.
+    fn load(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Baseline::default());
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read baseline from {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid baseline file {}", path.display()))
+    }
+
+    fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path, content)
+            .with_context(|| format!("Failed to write baseline to {}", path.display()))
+    }
+
+    fn key(day: u8, phase: Phase) -> String {
+        format!("day{:02}/{}", day, phase)
+    }
+}
+
+/// Run `f` the given number of times and collect the time each run took.
+fn sample<T>(iterations: u32, mut f: impl FnMut() -> anyhow::Result<T>) -> anyhow::Result<Vec<Duration>> {
+    let mut samples = Vec::with_capacity(iterations as usize);
+    for _ in 0..iterations {
+        let start = Instant::now();
+        black_box(f()?);
+        samples.push(start.elapsed());
+    }
+    Ok(samples)
+}
+
+/// Percentage change of the median compared to the baseline.
+fn change(stats: &Stats, baseline: &Stats) -> f64 {
+    (stats.median_ns as f64 / baseline.median_ns.max(1) as f64 - 1.0) * 100.0
+}
+
+fn format_ns(ns: u64) -> String {
+    format!("{:.2?}", Duration::from_nanos(ns))
+}
+
+pub fn run(args: &Args, bench_args: &BenchArgs) -> anyhow::Result<()> {
+    if bench_args.iterations == 0 {
+        return Err(anyhow!("--iterations must be at least 1."));
+    }
+    let parts = args.selected_parts();
+    let mut baseline = Baseline::load(&bench_args.baseline)?;
+
+    let mut results = Vec::new();
+    for entry in registry::select(args.selected_day()) {
+        let input = args.load_input(entry.day)?;
+        let solution = entry.solution;
+
+        let samples = sample(bench_args.iterations, || solution.parse_dyn(&input))
+            .map_err(|e| e.context(format!("Day {}", entry.day)))?;
+        results.push((entry.day, Phase::Parse, Stats::from_samples(samples)));
+
+        let parsed = solution.parse_dyn(&input)?;
+        for &part in &parts {
+            let samples = sample(bench_args.iterations, || solution.solve_dyn(part, parsed.as_ref()))
+                .map_err(|e| e.context(format!("Day {} part {}", entry.day, part)))?;
+            results.push((entry.day, Phase::Solve(part), Stats::from_samples(samples)));
+        }
+    }
+
+    if results.is_empty() {
+        return Err(anyhow!("No solution matches the selection."));
+    }
+
+    println!("{} iterations per phase", bench_args.iterations);
+    println!("{:>3} | {:<5} | {:>10} | {:>10} | {:>10} | Baseline", "Day", "Phase", "Min", "Median", "p95");
+    println!("{:-<3}-+-{:-<5}-+-{:-<10}-+-{:-<10}-+-{:-<10}-+---------", "", "", "", "", "");
+    let mut regressions = 0;
+    for (day, phase, stats) in &results {
+        let comparison = match baseline.0.get(&Baseline::key(*day, *phase)) {
+            Some(old) => {
+                let change = change(stats, old);
+                if change > bench_args.threshold {
+                    regressions += 1;
+                    format!("{:+.1}% REGRESSION", change)
+                } else {
+                    format!("{:+.1}%", change)
+                }
+            }
+            None => "new".to_owned(),
+        };
+        println!(
+            "{:>3} | {:<5} | {:>10} | {:>10} | {:>10} | {}",
+            day, phase, format_ns(stats.min_ns), format_ns(stats.median_ns), format_ns(stats.p95_ns), comparison
+        );
+    }
+
+    if bench_args.save_baseline {
+        for (day, phase, stats) in results {
+            baseline.0.insert(Baseline::key(day, phase), stats);
+        }
+        baseline.save(&bench_args.baseline)?;
+    }
+    if regressions > 0 {
+        println!("{} phase(s) regressed by more than {}%.", regressions, bench_args.threshold);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn stats_from_samples() {
+        let samples = (1..=100).rev().map(Duration::from_nanos).collect();
+        let stats = Stats::from_samples(samples);
+        assert_eq!(stats, Stats { min_ns: 1, median_ns: 50, p95_ns: 95 });
+    }
+
+    #[test]
+    fn change_against_baseline() {
+        let old = Stats { min_ns: 1, median_ns: 100, p95_ns: 200 };
+        let new = Stats { min_ns: 1, median_ns: 150, p95_ns: 200 };
+        assert_eq!(change(&new, &old), 50.0);
+        assert_eq!(Baseline::key(3, Phase::Solve(Part::Two)), "day03/part2");
+    }
+}
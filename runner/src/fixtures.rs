@@ -0,0 +1,214 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{anyhow, Context};
+use clap::Args as ClapArgs;
+use common::Part;
+use common::parse::ints;
+use crate::Args;
+
+#[derive(ClapArgs)]
+pub struct ExamplesArgs {
+    /// The saved puzzle description page.
+    page: PathBuf,
+
+    /// The directory the fixtures are written to.
+    #[arg(long, default_value = "fixtures")]
+    out: PathBuf,
+}
+
+/// An example input together with the answer the puzzle description states for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fixture {
+    pub part: Part,
+    pub input: String,
+    pub answer: String,
+}
+
+/// Directory holding the fixtures of a day.
+fn day_dir(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("day{:02}", day))
+}
+
+fn part_name(part: Part) -> String {
+    format!("part{}", part)
+}
+
+/// Replace the HTML entities AoC pages use with the characters they stand for.
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Remove all tags, e.g. the `<em>` highlighting inside of example blocks.
+fn strip_tags(s: &str) -> String {
+    let mut res = String::with_capacity(s.len());
+    let mut in_tag = false;
+    for c in s.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => res.push(c),
+            _ => {}
+        }
+    }
+    res
+}
+
+/// Get the contents of every `open ... close` section in the text, in order.
+fn sections<'a>(text: &'a str, open: &'a str, close: &'a str) -> impl Iterator<Item=&'a str> + 'a {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        let start = rest.find(open)? + open.len();
+        let end = start + rest[start..].find(close)?;
+        let section = &rest[start..end];
+        rest = &rest[end + close.len()..];
+        Some(section)
+    })
+}
+
+/// Get the day from the page title, e.g. `--- Day 3: Gear Ratios ---`.
+pub fn day_of_page(html: &str) -> Option<u8> {
+    let title = sections(html, "<h2>", "</h2>").next()?;
+    let (_, rest) = title.split_once("Day ")?;
+    let (day, _) = ints::<u8>(rest).next()?.ok()?;
+    Some(day)
+}
+
+/// Extract the examples from a saved puzzle description.
+///
+/// Every part is described in its own `<article>`. The first `<pre><code>` block in it is the example
+/// input and the last highlighted `<code><em>` is the answer for it. Part two usually has no block of its
+/// own and reuses the example of part one.
+pub fn extract(html: &str) -> Vec<Fixture> {
+    let mut fixtures: Vec<Fixture> = Vec::new();
+    let parts = [Part::One, Part::Two];
+    for (article, part) in sections(html, "<article", "</article>").zip(parts) {
+        let input = sections(article, "<pre><code>", "</code></pre>")
+            .next()
+            .map(|block| decode_entities(&strip_tags(block)))
+            .or_else(|| fixtures.last().map(|f| f.input.clone()));
+        let answer = sections(article, "<code><em>", "</em></code>")
+            .last()
+            .map(|a| decode_entities(&strip_tags(a)));
+        if let Some((input, answer)) = input.zip(answer) {
+            fixtures.push(Fixture {
+                part,
+                input,
+                answer,
+            });
+        }
+    }
+    fixtures
+}
+
+/// Write fixtures as `dayNN/partN.txt` holding the input and `dayNN/partN.answer` holding the answer.
+pub fn save(dir: &Path, day: u8, fixtures: &[Fixture]) -> anyhow::Result<()> {
+    let dir = day_dir(dir, day);
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create {}", dir.display()))?;
+    for fixture in fixtures {
+        let name = part_name(fixture.part);
+        fs::write(dir.join(format!("{}.txt", name)), &fixture.input)?;
+        fs::write(dir.join(format!("{}.answer", name)), format!("{}\n", fixture.answer))?;
+    }
+    Ok(())
+}
+
+pub fn run(args: &Args, examples_args: &ExamplesArgs) -> anyhow::Result<()> {
+    let html = fs::read_to_string(&examples_args.page)
+        .with_context(|| format!("Failed to read {}", examples_args.page.display()))?;
+    let day = args.selected_day()
+        .or_else(|| day_of_page(&html))
+        .ok_or(anyhow!("Could not find the day in the page. Pass it with --day."))?;
+    let fixtures = extract(&html);
+    if fixtures.is_empty() {
+        return Err(anyhow!("The page contains no examples with answers."));
+    }
+    save(&examples_args.out, day, &fixtures)?;
+    for fixture in &fixtures {
+        println!("Day {} part {}: example with answer {}", day, fixture.part, fixture.answer);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::registry;
+
+    /// Load all fixtures of a day. A day without fixtures has none.
+    fn load(dir: &Path, day: u8) -> anyhow::Result<Vec<Fixture>> {
+        let dir = day_dir(dir, day);
+        let mut fixtures = Vec::new();
+        for part in [Part::One, Part::Two] {
+            let name = part_name(part);
+            let input_path = dir.join(format!("{}.txt", name));
+            if !input_path.exists() {
+                continue;
+            }
+            let answer_path = dir.join(format!("{}.answer", name));
+            let answer = fs::read_to_string(&answer_path)
+                .with_context(|| format!("Missing answer {}", answer_path.display()))?;
+            fixtures.push(Fixture {
+                part,
+                input: fs::read_to_string(&input_path)?,
+                answer: answer.trim().to_owned(),
+            });
+        }
+        Ok(fixtures)
+    }
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 3: Gear Ratios ---</h2><p>Here is an example engine schematic:</p>
+<pre><code>467..114..
+...*......
+..35..633.
+......#...
+617<em>*</em>......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+</code></pre>
+<p>In this schematic, two numbers are <em>not</em> part numbers. The sum is <code><em>4361</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>544664</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Adding up all of the gear ratios produces <code><em>467835</em></code>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn extract_from_page() {
+        assert_eq!(day_of_page(PAGE), Some(3));
+        let fixtures = extract(PAGE);
+        assert_eq!(fixtures.len(), 2);
+        assert!(fixtures[0].input.starts_with("467..114..\n"));
+        assert!(fixtures[0].input.contains("617*......"));
+        assert_eq!(fixtures[0].answer, "4361");
+        assert_eq!(fixtures[1].input, fixtures[0].input);
+        assert_eq!((fixtures[1].part, fixtures[1].answer.as_str()), (Part::Two, "467835"));
+    }
+
+    #[test]
+    fn entities() {
+        assert_eq!(decode_entities(&strip_tags("&lt;<em>x</em>&gt; &amp;lt;")), "<x> &lt;");
+    }
+
+    /// Every solution has to produce the stated answer for the examples in the fixtures directory.
+    #[test]
+    fn solutions_match_fixtures() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../fixtures");
+        for entry in registry::REGISTRY {
+            for fixture in load(&dir, entry.day).unwrap() {
+                let parsed = entry.solution.parse_dyn(&fixture.input).unwrap();
+                let answer = entry.solution.solve_dyn(fixture.part, parsed.as_ref()).unwrap();
+                assert_eq!(answer, fixture.answer, "Day {} part {}", entry.day, fixture.part);
+            }
+        }
+    }
+}
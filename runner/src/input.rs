@@ -0,0 +1,88 @@
+use std::fs;
+use std::io::{BufRead, BufReader, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+use anyhow::{anyhow, Context};
+
+/// Where the puzzle input of a day is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+}
+
+/// The conventional location of a day's input inside the input directory.
+pub fn default_path(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("day{:02}.txt", day))
+}
+
+/// Decide where the input of a day comes from.
+///
+/// An explicit file always wins, `-` meaning stdin. Otherwise the day's file in the input directory
+/// is used. If that does not exist, stdin is used as a fallback when it is piped and only one day
+/// is being run.
+pub fn resolve(explicit: Option<&Path>, dir: &Path, day: u8, single_day: bool) -> anyhow::Result<Source> {
+    if let Some(path) = explicit {
+        return if path == Path::new("-") {
+            Ok(Source::Stdin)
+        } else {
+            Ok(Source::File(path.to_owned()))
+        };
+    }
+
+    let path = default_path(dir, day);
+    if path.is_file() {
+        Ok(Source::File(path))
+    } else if single_day && !std::io::stdin().is_terminal() {
+        Ok(Source::Stdin)
+    } else {
+        Err(anyhow!(
+            "Missing input for day {}: {} does not exist. Pass --input, set AOC_INPUT_DIR or pipe the input through stdin.",
+            day,
+            path.display()
+        ))
+    }
+}
+
+/// Read the whole input from its source.
+pub fn load(source: &Source) -> anyhow::Result<String> {
+    match source {
+        Source::File(path) => fs::read_to_string(path)
+            .with_context(|| format!("Failed to read input file {}", path.display())),
+        Source::Stdin => {
+            let mut buf = String::new();
+            std::io::stdin().read_to_string(&mut buf).context("Failed to read input from stdin")?;
+            Ok(buf)
+        }
+    }
+}
+
+/// Open the source for reading it line by line, without loading it whole.
+pub fn open(source: &Source) -> anyhow::Result<Box<dyn BufRead>> {
+    match source {
+        Source::File(path) => {
+            let file = fs::File::open(path)
+                .with_context(|| format!("Failed to open input file {}", path.display()))?;
+            Ok(Box::new(BufReader::new(file)))
+        }
+        Source::Stdin => Ok(Box::new(std::io::stdin().lock())),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn explicit_path_wins() {
+        let source = resolve(Some(Path::new("my_input.txt")), Path::new("inputs"), 3, false).unwrap();
+        assert_eq!(source, Source::File(PathBuf::from("my_input.txt")));
+        let source = resolve(Some(Path::new("-")), Path::new("inputs"), 3, false).unwrap();
+        assert_eq!(source, Source::Stdin);
+    }
+
+    #[test]
+    fn missing_file_is_an_error() {
+        let err = resolve(None, Path::new("does/not/exist"), 3, false).unwrap_err();
+        assert!(err.to_string().contains("day03.txt"));
+    }
+}
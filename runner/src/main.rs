@@ -0,0 +1,312 @@
+mod answers;
+mod bench;
+mod fixtures;
+mod input;
+mod output;
+mod registry;
+
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Instant;
+use anyhow::anyhow;
+use clap::{Parser, Subcommand};
+use common::{ParseError, Part};
+use crate::answers::{Answers, Check};
+use crate::output::{Format, Row};
+
+/// Run Advent of Code 2023 solutions.
+#[derive(Parser)]
+#[command(name = "aoc")]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// The day to run, or `all`.
+    #[arg(long, global = true, default_value = "all")]
+    day: Selection<u8>,
+
+    /// The part to run (1 or 2), or `all`.
+    #[arg(long, global = true, default_value = "all")]
+    part: Selection<PartArg>,
+
+    /// Read the input from this file instead, `-` for stdin. Requires a single day.
+    #[arg(long, global = true)]
+    input: Option<PathBuf>,
+
+    /// The directory containing the inputs, named `dayNN.txt`.
+    #[arg(long, global = true, env = "AOC_INPUT_DIR", default_value = "inputs")]
+    input_dir: PathBuf,
+
+    /// The file of accepted answers that every run is checked against.
+    #[arg(long, default_value = "answers.toml")]
+    answers: PathBuf,
+
+    /// Record the answers of this run as accepted, unless an answer is already recorded.
+    #[arg(long)]
+    accept: bool,
+
+    /// How the results are printed.
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Stream the input line by line through the days that support it, instead of reading it whole. The
+    /// other days read it whole as usual.
+    #[arg(long)]
+    stream: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the parse and solve phases many times and report timing statistics.
+    Bench(bench::BenchArgs),
+    /// Extract the examples from a saved puzzle page into fixture files for the tests.
+    Examples(fixtures::ExamplesArgs),
+}
+
+impl Args {
+
+    fn selected_day(&self) -> Option<u8> {
+        self.day.into_option()
+    }
+
+    fn selected_parts(&self) -> Vec<Part> {
+        match self.part.into_option() {
+            Some(PartArg(part)) => vec![part],
+            None => vec![Part::One, Part::Two],
+        }
+    }
+
+    /// Where the input of a day comes from, according to the arguments.
+    fn input_source(&self, day: u8) -> anyhow::Result<input::Source> {
+        let single_day = self.selected_day().is_some();
+        input::resolve(self.input.as_deref(), &self.input_dir, day, single_day)
+    }
+
+    /// Load the input of a day from wherever the arguments say it comes from.
+    fn load_input(&self, day: u8) -> anyhow::Result<String> {
+        input::load(&self.input_source(day)?)
+    }
+}
+
+/// Either a single value or `all`.
+#[derive(Clone, Copy)]
+enum Selection<T> {
+    All,
+    One(T),
+}
+
+impl<T> Selection<T> {
+    fn into_option(self) -> Option<T> {
+        match self {
+            Selection::All => None,
+            Selection::One(t) => Some(t),
+        }
+    }
+}
+
+impl<T: FromStr> FromStr for Selection<T> {
+    type Err = T::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("all") {
+            Ok(Selection::All)
+        } else {
+            T::from_str(s).map(Selection::One)
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct PartArg(Part);
+
+impl FromStr for PartArg {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(PartArg(Part::One)),
+            "2" => Ok(PartArg(Part::Two)),
+            _ => Err(anyhow!("Invalid part '{}'. Expected 1, 2 or all.", s)),
+        }
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    if args.input.is_some() && args.selected_day().is_none() {
+        return Err(anyhow!("--input requires a single --day."));
+    }
+    match &args.command {
+        None => run(&args),
+        Some(Command::Bench(bench_args)) => bench::run(&args, bench_args),
+        Some(Command::Examples(examples_args)) => fixtures::run(&args, examples_args),
+    }
+}
+
+/// Format an error for the output. Errors in the input are rendered as a diagnostic that points at
+/// the offending token.
+fn describe_error(e: &anyhow::Error) -> String {
+    match e.chain().find_map(|cause| cause.downcast_ref::<ParseError>()) {
+        Some(parse_error) => parse_error.render(),
+        None => format!("{:#}", e),
+    }
+}
+
+/// Solve the selected days once and check the answers.
+///
+/// A day that fails is reported in its rows and does not stop the other days from running.
+fn run(args: &Args) -> anyhow::Result<()> {
+    let parts = args.selected_parts();
+    let mut answers = Answers::load(&args.answers)?;
+
+    let mut rows = Vec::new();
+    for entry in registry::select(args.selected_day()) {
+        rows.extend(solve_day(args, entry, &parts, &answers));
+    }
+
+    if rows.is_empty() {
+        return Err(anyhow!("No solution matches the selection."));
+    }
+    output::print(args.format, &rows)?;
+
+    if args.accept {
+        let mut changed = false;
+        for row in &rows {
+            if let Ok(answer) = &row.answer {
+                changed |= answers.accept(row.day, row.part, answer);
+            }
+        }
+        if changed {
+            answers.save(&args.answers)?;
+        }
+    }
+
+    let failed = rows.iter().filter(|r| r.answer.is_err()).count();
+    let wrong = rows.iter().filter(|r| matches!(r.check, Some(Check::Wrong { .. }))).count();
+    if failed > 0 {
+        return Err(anyhow!("{} part(s) failed.", failed));
+    }
+    if wrong > 0 {
+        return Err(anyhow!("{} answer(s) differ from the accepted ones in {}.", wrong, args.answers.display()));
+    }
+    Ok(())
+}
+
+/// Solve the parts of one day and check the answers. Failures are reported in the rows.
+fn solve_day(args: &Args, entry: &registry::Entry, parts: &[Part], answers: &Answers) -> Vec<Row> {
+    let answer_type = entry.solution.answer_type();
+    let failed = |e: anyhow::Error| parts.iter().map(move |&part| Row {
+        day: entry.day,
+        part,
+        answer_type,
+        answer: Err(describe_error(&e)),
+        parse_time: None,
+        solve_time: None,
+        check: None,
+    }).collect::<Vec<_>>();
+
+    if args.stream {
+        if let Some(rows) = stream_day(args, entry, parts, answers) {
+            return rows;
+        }
+    }
+
+    let input = match args.load_input(entry.day) {
+        Ok(input) => input,
+        Err(e) => return failed(e),
+    };
+
+    let start = Instant::now();
+    let parsed = entry.solution.parse_dyn(&input);
+    let parse_time = Some(start.elapsed());
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => return failed(e).into_iter().map(|row| Row { parse_time, ..row }).collect(),
+    };
+
+    let mut rows = Vec::new();
+    for &part in parts {
+        let start = Instant::now();
+        let answer = entry.solution.solve_dyn(part, parsed.as_ref());
+        let solve_time = Some(start.elapsed());
+        let check = answer.as_ref().ok().map(|a| answers.check(entry.day, part, a));
+        rows.push(Row {
+            day: entry.day,
+            part,
+            answer_type,
+            answer: answer.map_err(|e| describe_error(&e)),
+            parse_time,
+            solve_time,
+            check,
+        });
+    }
+    rows
+}
+
+/// Solve the parts of one day while reading its input line by line. `None` if the day does not support
+/// streaming, in which case nothing has been read from the input yet.
+fn stream_day(args: &Args, entry: &registry::Entry, parts: &[Part], answers: &Answers) -> Option<Vec<Row>> {
+    let mut rows = Vec::new();
+    for (idx, &part) in parts.iter().enumerate() {
+        let start = Instant::now();
+        let answer = stream_part(args, entry, part, idx > 0)?;
+        let solve_time = Some(start.elapsed());
+        let check = answer.as_ref().ok().map(|a| answers.check(entry.day, part, a));
+        rows.push(Row {
+            day: entry.day,
+            part,
+            answer_type: entry.solution.answer_type(),
+            answer: answer.map_err(|e| describe_error(&e)),
+            parse_time: None,
+            solve_time,
+            check,
+        });
+    }
+    Some(rows)
+}
+
+/// Solve one part while reading its input line by line. Every part reads the input from the start,
+/// which stdin cannot do more than once, so `again` has to be set for all parts but the first one.
+/// `None` if the day does not support streaming.
+fn stream_part(args: &Args, entry: &registry::Entry, part: Part, again: bool) -> Option<anyhow::Result<String>> {
+    let reader = args.input_source(entry.day).and_then(|source| {
+        if again && source == input::Source::Stdin {
+            return Err(anyhow!("Streaming from stdin requires a single --part."));
+        }
+        input::open(&source)
+    });
+    match reader {
+        Ok(mut reader) => entry.solution.stream_dyn(part, &mut reader),
+        Err(e) => Some(Err(e)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::path::Path;
+    use super::*;
+
+    #[test]
+    fn streaming_falls_back_for_other_days() {
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("../fixtures");
+        let dir = std::env::temp_dir().join(format!("aoc-stream-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for day in [1, 2] {
+            fs::copy(fixtures.join(format!("day{:02}/part1.txt", day)), input::default_path(&dir, day)).unwrap();
+        }
+        let args = Args::parse_from(["aoc", "--stream", "--part", "1", "--input-dir", dir.to_str().unwrap()]);
+        let parts = args.selected_parts();
+        let answers = Answers::default();
+
+        let days: Vec<_> = registry::select(None).take(2).collect();
+        assert!(stream_day(&args, days[0], &parts, &answers).is_none());
+        assert!(stream_day(&args, days[1], &parts, &answers).is_some());
+        for entry in days {
+            let rows = solve_day(&args, entry, &parts, &answers);
+            let expected = fs::read_to_string(fixtures.join(format!("day{:02}/part1.answer", entry.day))).unwrap();
+            assert_eq!(rows[0].answer.as_deref(), Ok(expected.trim()), "Day {}", entry.day);
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
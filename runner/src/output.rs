@@ -0,0 +1,156 @@
+use std::time::Duration;
+use clap::ValueEnum;
+use common::Part;
+use serde::Serialize;
+use crate::answers::Check;
+
+/// How the results of a run are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// A human readable table.
+    Text,
+    /// One JSON record per line (NDJSON).
+    Json,
+}
+
+/// The outcome of solving one part of a day.
+pub struct Row {
+    pub day: u8,
+    pub part: Part,
+    pub answer_type: &'static str,
+    /// The answer, or the error that prevented it.
+    pub answer: Result<String, String>,
+    pub parse_time: Option<Duration>,
+    pub solve_time: Option<Duration>,
+    pub check: Option<Check>,
+}
+
+/// The JSON representation of a [Row].
+#[derive(Serialize)]
+struct Record<'a> {
+    day: u8,
+    part: u8,
+    answer: Option<&'a str>,
+    answer_type: &'a str,
+    parse_time_ns: Option<u64>,
+    solve_time_ns: Option<u64>,
+    check: Option<&'static str>,
+    expected: Option<&'a str>,
+    error: Option<&'a str>,
+}
+
+impl<'a> From<&'a Row> for Record<'a> {
+    fn from(row: &'a Row) -> Self {
+        let (check, expected) = match &row.check {
+            Some(Check::Correct) => (Some("ok"), None),
+            Some(Check::Wrong { expected }) => (Some("wrong"), Some(expected.as_str())),
+            Some(Check::Unknown) => (Some("new"), None),
+            None => (None, None),
+        };
+        Record {
+            day: row.day,
+            part: row.part.into(),
+            answer: row.answer.as_deref().ok(),
+            answer_type: row.answer_type,
+            parse_time_ns: row.parse_time.map(|t| t.as_nanos() as u64),
+            solve_time_ns: row.solve_time.map(|t| t.as_nanos() as u64),
+            check,
+            expected,
+            error: row.answer.as_ref().err().map(String::as_str),
+        }
+    }
+}
+
+pub fn print(format: Format, rows: &[Row]) -> anyhow::Result<()> {
+    match format {
+        Format::Text => print_table(rows),
+        Format::Json => print_ndjson(rows)?,
+    }
+    Ok(())
+}
+
+fn print_table(rows: &[Row]) {
+    let answer_text = |row: &Row| -> String {
+        match &row.answer {
+            Ok(answer) => answer.clone(),
+            Err(_) => "-".to_owned(),
+        }
+    };
+    let answer_width = rows.iter()
+        .map(|r| answer_text(r).len())
+        .max()
+        .unwrap_or(0)
+        .max("Answer".len());
+    println!("{:>3} | {:>4} | {:<answer_width$} | {:>10} | {:>10} | Check", "Day", "Part", "Answer", "Parse", "Solve");
+    println!("{:-<3}-+-{:-<4}-+-{:-<answer_width$}-+-{:-<10}-+-{:-<10}-+------", "", "", "", "", "");
+    for row in rows {
+        let format_time = |t: Option<Duration>| t.map(|t| format!("{:.2?}", t)).unwrap_or("-".to_owned());
+        let parse_time = format_time(row.parse_time);
+        let solve_time = format_time(row.solve_time);
+        let check = match &row.check {
+            Some(check) => check.to_string(),
+            None => "ERROR".to_owned(),
+        };
+        println!("{:>3} | {:>4} | {:<answer_width$} | {:>10} | {:>10} | {}", row.day, row.part, answer_text(row), parse_time, solve_time, check);
+    }
+
+    // A day whose input failed to load or parse fails the same way for every part, so its error is only
+    // printed once.
+    let errors: Vec<_> = rows.iter()
+        .filter_map(|r| r.answer.as_ref().err().map(|e| (r.day, r.part, e)))
+        .collect();
+    for group in errors.chunk_by(|a, b| a.0 == b.0 && a.2 == b.2) {
+        println!();
+        match group {
+            [(day, part, e)] => println!("Day {} part {} failed:\n{}", day, part, e),
+            [(day, _, e), ..] => println!("Day {} failed:\n{}", day, e),
+            [] => {}
+        }
+    }
+}
+
+fn print_ndjson(rows: &[Row]) -> anyhow::Result<()> {
+    for row in rows {
+        println!("{}", serde_json::to_string(&Record::from(row))?);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn json_record() {
+        let row = Row {
+            day: 3,
+            part: Part::Two,
+            answer_type: "u32",
+            answer: Ok("467835".to_owned()),
+            parse_time: Some(Duration::from_nanos(1500)),
+            solve_time: Some(Duration::from_nanos(20)),
+            check: Some(Check::Wrong { expected: "1".to_owned() }),
+        };
+        assert_eq!(
+            serde_json::to_string(&Record::from(&row)).unwrap(),
+            r#"{"day":3,"part":2,"answer":"467835","answer_type":"u32","parse_time_ns":1500,"solve_time_ns":20,"check":"wrong","expected":"1","error":null}"#
+        );
+    }
+
+    #[test]
+    fn json_record_with_error() {
+        let row = Row {
+            day: 1,
+            part: Part::One,
+            answer_type: "u32",
+            answer: Err("Invalid input".to_owned()),
+            parse_time: None,
+            solve_time: None,
+            check: None,
+        };
+        let json = serde_json::to_string(&Record::from(&row)).unwrap();
+        assert!(json.contains(r#""answer":null"#));
+        assert!(json.contains(r#""parse_time_ns":null"#));
+        assert!(json.contains(r#""error":"Invalid input""#));
+    }
+}
@@ -0,0 +1,40 @@
+use common::DynSolution;
+
+/// A solved puzzle day.
+pub struct Entry {
+    pub day: u8,
+    pub solution: &'static dyn DynSolution,
+}
+
+/// All solved days, in order.
+pub static REGISTRY: &[Entry] = &[
+    Entry { day: 1, solution: &day01::Day01 },
+    Entry { day: 2, solution: &day02::Day02 },
+    Entry { day: 3, solution: &day03::Day03 },
+    Entry { day: 4, solution: &day04::Day04 },
+];
+
+/// Get all registry entries that match the selection. `None` selects every day.
+pub fn select(day: Option<u8>) -> impl Iterator<Item=&'static Entry> {
+    REGISTRY.iter()
+        .filter(move |e| day.is_none_or(|d| e.day == d))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn registry_matches_solutions() {
+        for entry in REGISTRY {
+            assert_eq!(entry.day, entry.solution.day());
+        }
+    }
+
+    #[test]
+    fn select_by_day() {
+        assert_eq!(select(None).count(), REGISTRY.len());
+        assert_eq!(select(Some(3)).next().unwrap().day, 3);
+        assert_eq!(select(Some(26)).count(), 0);
+    }
+}